@@ -0,0 +1,154 @@
+use super::*;
+use cnf::{Lit, Tseitin};
+use num_bigint::BigUint;
+use sat::Solver;
+use std::fmt;
+use traverser::Arguments;
+
+#[cfg(test)]
+mod tests;
+
+/// Partial assignment of the arguments of an instruction. The arguments without a value are
+/// don't-cares, so the cube covers every row with any value for them.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Cube {
+    values: Vec<(String, Option<bool>)>,
+}
+
+impl Cube {
+    pub fn iter(&self) -> impl Iterator<Item = (&str, Option<bool>)> {
+        self.values.iter().map(|(k, v)| (k.as_str(), *v))
+    }
+
+    /// Number of arguments without a value.
+    pub fn dont_cares(&self) -> usize {
+        self.values.iter().filter(|(_, v)| v.is_none()).count()
+    }
+
+    /// Number of rows of the truth table covered by the cube.
+    pub fn rows(&self) -> BigUint {
+        BigUint::from(1u8) << self.dont_cares()
+    }
+
+    /// Whether the assignment agrees with every value of the cube.
+    pub fn covers<A>(&self, assignment: &A) -> bool
+    where
+        A: Assignment + ?Sized,
+    {
+        self.values
+            .iter()
+            .all(|(k, v)| v.is_none() || assignment.value(k) == *v)
+    }
+}
+
+impl fmt::Display for Cube {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let values = self
+            .values
+            .iter()
+            .map(|(k, v)| match v {
+                Some(v) => format!("{k}={}", u8::from(*v)),
+                None => format!("{k}=-"),
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        write!(f, "{values}")
+    }
+}
+
+/// Lazy enumeration of the models of an instruction as disjoint cubes.
+///
+/// Every model found by the solver is shrunk to a cube by dropping the values that are not
+/// needed: a value is dropped if the remaining ones still imply the instruction, which is checked
+/// by solving its negation under them, and if the cube remains disjoint from the previous ones.
+/// The cube is then blocked, so the next model is outside of every cube so far. The cubes
+/// partition the models, and their rows add up to the model count.
+pub struct Cubes {
+    arguments: Vec<String>,
+    solver: Solver,
+    negation: Solver,
+    cubes: Vec<Vec<Lit>>,
+    done: bool,
+}
+
+impl Cubes {
+    pub fn new(instruction: &Instruction) -> Self {
+        let arguments = Arguments::sorted(instruction);
+
+        let mut tseitin = Tseitin::new(arguments.clone());
+        let root = tseitin.encode(instruction);
+        tseitin.assert(root);
+        let cnf = tseitin.finish();
+
+        let mut tseitin = Tseitin::new(arguments);
+        let root = tseitin.encode(instruction);
+        tseitin.assert(!root);
+        let negation = tseitin.finish();
+
+        Self {
+            arguments: cnf.arguments.clone(),
+            solver: Solver::with_cnf(&cnf),
+            negation: Solver::with_cnf(&negation),
+            cubes: vec![],
+            done: false,
+        }
+    }
+
+    /// Drop the values of the model that are not needed to imply the instruction.
+    fn shrink(&mut self, mut cube: Vec<Lit>) -> Vec<Lit> {
+        let mut i = 0;
+
+        while i < cube.len() {
+            let lit = cube.remove(i);
+
+            let disjoint = self
+                .cubes
+                .iter()
+                .all(|previous| previous.iter().any(|l| cube.contains(&!*l)));
+
+            if disjoint && !self.negation.solve_with(&cube) {
+                continue;
+            }
+
+            cube.insert(i, lit);
+            i += 1;
+        }
+
+        cube
+    }
+}
+
+impl Iterator for Cubes {
+    type Item = Cube;
+
+    fn next(&mut self) -> Option<Cube> {
+        if self.done || !self.solver.solve() {
+            self.done = true;
+            return None;
+        }
+
+        let model = (0..self.arguments.len())
+            .map(|v| Lit::new(v, self.solver.model(v).unwrap_or(false)))
+            .collect();
+
+        let cube = self.shrink(model);
+        let blocking = cube.iter().map(|l| !*l).collect::<Vec<_>>();
+
+        self.done = !self.solver.add_clause(&blocking);
+
+        let mut values = self
+            .arguments
+            .iter()
+            .map(|a| (a.clone(), None))
+            .collect::<Vec<_>>();
+
+        for lit in &cube {
+            values[lit.variable()].1 = Some(lit.is_positive());
+        }
+
+        self.cubes.push(cube);
+
+        Some(Cube { values })
+    }
+}
@@ -0,0 +1,53 @@
+use super::*;
+
+#[test]
+fn cubes_partition_the_models() {
+    let exprs = [
+        "a v b",
+        "a -> (b ^ c)",
+        "!(a + b) = !c -> (x <-> a)",
+        "(a ^ b) v (c ^ d) v (e ^ f)",
+        "a + b + c",
+    ];
+
+    for expr in exprs {
+        let instruction = Instruction::try_from(expr).unwrap();
+        let cubes = instruction.cubes().collect::<Vec<_>>();
+
+        for ev in instruction.evaluations().unwrap() {
+            let covering = cubes.iter().filter(|c| c.covers(ev.as_ref())).count();
+            assert_eq!(usize::from(ev.result()), covering, "{expr}: {ev:?}");
+        }
+
+        let rows = cubes.iter().map(Cube::rows).sum::<BigUint>();
+        assert_eq!(instruction.count_models(), rows, "{expr}");
+    }
+}
+
+#[test]
+fn cubes_are_compact() {
+    let instruction = Instruction::try_from("a v b").unwrap();
+    let cubes = instruction.cubes().collect::<Vec<_>>();
+
+    assert_eq!(2, cubes.len());
+    assert_eq!(1, cubes.iter().map(Cube::dont_cares).sum::<usize>());
+    assert!(cubes.iter().any(|c| c.to_string().contains('-')));
+
+    assert_eq!(1, Instruction::try_from("a v !a").unwrap().cubes().count());
+    assert_eq!(0, Instruction::try_from("a ^ !a").unwrap().cubes().count());
+}
+
+#[test]
+fn cubes_are_lazy() {
+    // the full table has 2^200 rows
+    let expr = (0..100)
+        .map(|i| format!("(a{i} v b{i})"))
+        .collect::<Vec<_>>()
+        .join(" ^ ");
+
+    let instruction = Instruction::try_from(expr.as_str()).unwrap();
+    let cube = instruction.cubes().next().unwrap();
+
+    assert_eq!(200, cube.iter().count());
+    assert_eq!(100, cube.dont_cares());
+}
@@ -0,0 +1,74 @@
+use std::{
+    borrow::Borrow,
+    collections::{BTreeMap, HashMap},
+    hash::{BuildHasher, Hash},
+};
+
+#[cfg(test)]
+mod tests;
+
+/// Source of the values of the arguments of an instruction.
+///
+/// Implemented for maps and lists of `(name, value)` pairs, and for closures that resolve a name
+/// to its value.
+pub trait Assignment {
+    /// The value of the argument, or `None` if it is unbound.
+    fn value(&self, argument: &str) -> Option<bool>;
+}
+
+impl<K, S> Assignment for HashMap<K, bool, S>
+where
+    K: Borrow<str> + Hash + Eq,
+    S: BuildHasher,
+{
+    fn value(&self, argument: &str) -> Option<bool> {
+        self.get(argument).copied()
+    }
+}
+
+impl<K> Assignment for BTreeMap<K, bool>
+where
+    K: Borrow<str> + Ord,
+{
+    fn value(&self, argument: &str) -> Option<bool> {
+        self.get(argument).copied()
+    }
+}
+
+impl<K> Assignment for [(K, bool)]
+where
+    K: AsRef<str>,
+{
+    fn value(&self, argument: &str) -> Option<bool> {
+        self.iter()
+            .find(|(k, _)| k.as_ref() == argument)
+            .map(|(_, v)| *v)
+    }
+}
+
+impl<K, const N: usize> Assignment for [(K, bool); N]
+where
+    K: AsRef<str>,
+{
+    fn value(&self, argument: &str) -> Option<bool> {
+        self.as_slice().value(argument)
+    }
+}
+
+impl<K> Assignment for Vec<(K, bool)>
+where
+    K: AsRef<str>,
+{
+    fn value(&self, argument: &str) -> Option<bool> {
+        self.as_slice().value(argument)
+    }
+}
+
+impl<F> Assignment for F
+where
+    F: Fn(&str) -> Option<bool>,
+{
+    fn value(&self, argument: &str) -> Option<bool> {
+        self(argument)
+    }
+}
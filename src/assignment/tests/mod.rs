@@ -0,0 +1,47 @@
+use super::*;
+use crate::Instruction;
+
+#[test]
+fn evaluate_with_maps() {
+    let instruction = Instruction::try_from("a -> 'b c'").unwrap();
+
+    let map = HashMap::from([("a", true), ("b c", false)]);
+    assert!(!instruction.evaluate_with(&map).unwrap());
+
+    let map = HashMap::from([("a".to_string(), false), ("b c".to_string(), false)]);
+    assert!(instruction.evaluate_with(&map).unwrap());
+
+    let map = BTreeMap::from([("a", true), ("b c", true)]);
+    assert!(instruction.evaluate_with(&map).unwrap());
+}
+
+#[test]
+fn evaluate_with_pairs() {
+    let instruction = Instruction::try_from("a + b").unwrap();
+
+    assert!(instruction
+        .evaluate_with(&[("a", true), ("b", false)])
+        .unwrap());
+
+    let pairs = vec![("a".to_string(), true), ("b".to_string(), true)];
+    assert!(!instruction.evaluate_with(&pairs).unwrap());
+    assert!(!instruction.evaluate_with(pairs.as_slice()).unwrap());
+}
+
+#[test]
+fn evaluate_with_closure() {
+    let instruction = Instruction::try_from("flag_a ^ !flag_b").unwrap();
+    let resolver = |name: &str| Some(name.ends_with('a'));
+
+    assert!(instruction.evaluate_with(&resolver).unwrap());
+}
+
+#[test]
+fn evaluate_with_unbound_argument() {
+    let instruction = Instruction::try_from("a v (b ^ c)").unwrap();
+    let err = instruction
+        .evaluate_with(&[("a", true), ("c", false)])
+        .unwrap_err();
+
+    assert!(err.contains("`b`"), "{err}");
+}
@@ -0,0 +1,68 @@
+use super::*;
+use cnf::{Cnf, Lit};
+use sat::Solver;
+
+#[cfg(test)]
+mod tests;
+
+/// Finder of the literals that hold in every model of an instruction.
+///
+/// The first model is the set of candidates. Each candidate is then tested by solving under its
+/// negation: if that is unsatisfiable, the literal is in the backbone; otherwise the new model
+/// rules out every candidate it disagrees with, so most candidates are discarded without a call
+/// of their own.
+pub struct Backbone;
+
+impl Backbone {
+    /// The values forced on the unbound arguments of the instruction by the bound ones, in the
+    /// order of the arguments.
+    ///
+    /// Fails if no model of the instruction agrees with the assignment.
+    pub fn run<A>(instruction: &Instruction, assignment: &A) -> Result<Vec<(String, bool)>, String>
+    where
+        A: Assignment + ?Sized,
+    {
+        let cnf = Cnf::tseitin(instruction);
+        let mut solver = Solver::with_cnf(&cnf);
+
+        let mut assumptions = cnf
+            .arguments
+            .iter()
+            .enumerate()
+            .filter_map(|(v, name)| assignment.value(name).map(|value| Lit::new(v, value)))
+            .collect::<Vec<_>>();
+
+        if !solver.solve_with(&assumptions) {
+            return Err("no model of the instruction agrees with the assignment".to_string());
+        }
+
+        let mut candidates = (0..cnf.arguments.len())
+            .filter(|v| !assumptions.iter().any(|l| l.variable() == *v))
+            .map(|v| Lit::new(v, solver.model(v).unwrap_or(false)))
+            .collect::<Vec<_>>();
+
+        let mut backbone = vec![];
+        while let Some(candidate) = candidates.pop() {
+            assumptions.push(!candidate);
+
+            if solver.solve_with(&assumptions) {
+                assumptions.pop();
+                candidates.retain(|l| solver.model(l.variable()) == Some(l.is_positive()));
+            } else {
+                // the candidate holds in every remaining model, so it can be assumed
+                assumptions.pop();
+                assumptions.push(candidate);
+                backbone.push(candidate);
+            }
+        }
+
+        backbone.sort_unstable();
+
+        let backbone = backbone
+            .into_iter()
+            .map(|l| (cnf.arguments[l.variable()].clone(), l.is_positive()))
+            .collect();
+
+        Ok(backbone)
+    }
+}
@@ -0,0 +1,65 @@
+use super::*;
+
+fn pairs(values: &[(&str, bool)]) -> Vec<(String, bool)> {
+    values.iter().map(|(k, v)| (k.to_string(), *v)).collect()
+}
+
+#[test]
+fn backbone() {
+    let instruction = Instruction::try_from("a ^ (a -> b) ^ (c v d) ^ !e").unwrap();
+
+    assert_eq!(
+        pairs(&[("a", true), ("b", true), ("e", false)]),
+        instruction.backbone().unwrap()
+    );
+
+    let instruction = Instruction::try_from("a v b").unwrap();
+    assert!(instruction.backbone().unwrap().is_empty());
+
+    let instruction = Instruction::try_from("a ^ !a").unwrap();
+    assert!(instruction.backbone().is_err());
+}
+
+#[test]
+fn backbone_agrees_with_truth_table() {
+    let instruction =
+        Instruction::try_from("(a -> b) ^ (b -> c) ^ (c -> !d) ^ (a v d) ^ !(e = d)").unwrap();
+    let models = instruction
+        .evaluations()
+        .unwrap()
+        .into_iter()
+        .filter(|ev| ev.result())
+        .collect::<Vec<_>>();
+
+    let expected = models[0]
+        .iter()
+        .filter(|(k, v)| models.iter().all(|m| m.value(k) == Some(*v)))
+        .map(|(k, v)| (k.to_string(), *v))
+        .collect::<Vec<_>>();
+
+    assert_eq!(expected, instruction.backbone().unwrap());
+}
+
+#[test]
+fn implied_literals() {
+    // pick one of three exclusive options, where x requires w and y excludes it
+    let instruction = Instruction::try_from(
+        "(x v y v z) ^ !(x ^ y) ^ !(x ^ z) ^ !(y ^ z) ^ (x -> w) ^ (y -> !w)",
+    )
+    .unwrap();
+
+    assert_eq!(
+        pairs(&[("w", true), ("y", false), ("z", false)]),
+        instruction.implied(&[("x", true)]).unwrap()
+    );
+    assert_eq!(
+        pairs(&[("w", false), ("x", false), ("z", false)]),
+        instruction.implied(&[("y", true)]).unwrap()
+    );
+    assert_eq!(
+        pairs(&[("x", false)]),
+        instruction.implied(&[("w", false)]).unwrap()
+    );
+
+    assert!(instruction.implied(&[("x", true), ("y", true)]).is_err());
+}
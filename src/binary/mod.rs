@@ -0,0 +1,318 @@
+use super::*;
+use std::collections::HashMap;
+
+#[cfg(test)]
+mod tests;
+
+/// Leading bytes of every encoded instruction.
+pub const MAGIC: &[u8; 2] = b"TF";
+
+/// Version of the binary format.
+pub const VERSION: u8 = 1;
+
+/// Deepest nesting of operators accepted by the encoder and the decoder, so a crafted input
+/// cannot build an instruction too deep to drop or compare without overflowing the stack.
+pub const MAX_DEPTH: usize = 1024;
+
+const OP_TRUE: u8 = 0x00;
+const OP_FALSE: u8 = 0x01;
+const OP_ARGUMENT: u8 = 0x02;
+const OP_NOT: u8 = 0x03;
+const OP_AND: u8 = 0x04;
+const OP_OR: u8 = 0x05;
+const OP_XOR: u8 = 0x06;
+const OP_CONDITIONAL: u8 = 0x07;
+const OP_BICONDITIONAL: u8 = 0x08;
+const OP_EQUALS: u8 = 0x09;
+
+/// Compact binary encoding of an instruction.
+///
+/// The layout is:
+///
+/// - the [`MAGIC`] bytes followed by the [`VERSION`] byte;
+/// - the number of identifiers, followed by every identifier as its length and UTF-8 bytes;
+/// - the number of opcodes, followed by the opcodes in postfix order. The argument opcode is
+///   followed by the index of its identifier.
+///
+/// Every length, count and index is an unsigned LEB128 varint. Identifiers are stored once, in
+/// order of first appearance, regardless of how many times they are referenced. Operators may be
+/// nested at most [`MAX_DEPTH`] levels deep, both when encoding and decoding.
+pub struct Binary;
+
+impl Binary {
+    /// Fails if the operators are nested more than [`MAX_DEPTH`] levels deep, since the bytes
+    /// could not be decoded.
+    pub fn encode(instruction: &Instruction) -> Result<Vec<u8>, String> {
+        let mut identifiers = Vec::new();
+        let mut indexes = HashMap::new();
+        let mut opcodes = Vec::new();
+        let mut count = 0;
+
+        Self::encode_postfix(
+            instruction,
+            &mut identifiers,
+            &mut indexes,
+            &mut opcodes,
+            &mut count,
+        )?;
+
+        let mut bytes = Vec::with_capacity(opcodes.len() + 16);
+        bytes.extend_from_slice(MAGIC);
+        bytes.push(VERSION);
+
+        write_varint(&mut bytes, identifiers.len());
+        for identifier in identifiers {
+            write_varint(&mut bytes, identifier.len());
+            bytes.extend_from_slice(identifier.as_bytes());
+        }
+
+        write_varint(&mut bytes, count);
+        bytes.extend(opcodes);
+
+        Ok(bytes)
+    }
+
+    /// Write the opcodes in postfix order, with an explicit stack so that deep instructions do
+    /// not overflow the call stack.
+    fn encode_postfix<'a>(
+        instruction: &'a Instruction,
+        identifiers: &mut Vec<&'a str>,
+        indexes: &mut HashMap<&'a str, usize>,
+        opcodes: &mut Vec<u8>,
+        count: &mut usize,
+    ) -> Result<(), String> {
+        // every node is visited twice: first to push its operands, then to write its opcode. It
+        // is paired with the number of operators above it
+        let mut pending = vec![(instruction, false, 0)];
+
+        while let Some((instruction, expanded, level)) = pending.pop() {
+            use Instruction::*;
+            let (op, l, r) = match instruction {
+                True => (OP_TRUE, None, None),
+                False => (OP_FALSE, None, None),
+
+                Argument(a) => {
+                    let index = *indexes.entry(a.as_str()).or_insert_with(|| {
+                        identifiers.push(a.as_str());
+                        identifiers.len() - 1
+                    });
+
+                    *count += 1;
+                    opcodes.push(OP_ARGUMENT);
+                    write_varint(opcodes, index);
+                    continue;
+                }
+
+                Not(x) => (OP_NOT, Some(x), None),
+                And(l, r) => (OP_AND, Some(l), Some(r)),
+                Or(l, r) => (OP_OR, Some(l), Some(r)),
+                Xor(l, r) => (OP_XOR, Some(l), Some(r)),
+                Conditional(l, r) => (OP_CONDITIONAL, Some(l), Some(r)),
+                Biconditional(l, r) => (OP_BICONDITIONAL, Some(l), Some(r)),
+                Equals(l, r) => (OP_EQUALS, Some(l), Some(r)),
+            };
+
+            if expanded || l.is_none() {
+                *count += 1;
+                opcodes.push(op);
+                continue;
+            }
+
+            if level == MAX_DEPTH {
+                return Err(format!(
+                    "the operators are nested more than {MAX_DEPTH} levels deep"
+                ));
+            }
+
+            pending.push((instruction, true, level));
+            for x in [r, l].into_iter().flatten() {
+                pending.push((x, false, level + 1));
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn decode(bytes: &[u8]) -> Result<Instruction, String> {
+        let mut reader = Reader { bytes, position: 0 };
+
+        if reader.take(MAGIC.len())? != MAGIC {
+            return Err("invalid binary header".to_string());
+        }
+
+        let version = reader.byte()?;
+        if version != VERSION {
+            return Err(format!("unsupported binary version {version}"));
+        }
+
+        let len = reader.varint()?;
+        let mut identifiers = Vec::with_capacity(len.min(reader.remaining()));
+        for _ in 0..len {
+            let size = reader.varint()?;
+            let identifier = reader.take(size)?;
+            let identifier = String::from_utf8(identifier.to_vec())
+                .map_err(|_| "identifier is not valid UTF-8".to_string())?;
+
+            if identifiers.contains(&identifier) {
+                return Err(format!("duplicated identifier `{identifier}`"));
+            }
+
+            identifiers.push(identifier);
+        }
+
+        let count = reader.varint()?;
+        if count == 0 {
+            return Err("the binary contains no opcodes".to_string());
+        }
+
+        let mut referenced = vec![false; identifiers.len()];
+        // every operand is paired with its depth, the number of operators it nests
+        let mut stack: Vec<(Instruction, usize)> =
+            Vec::with_capacity(count.min(reader.remaining()));
+
+        for _ in 0..count {
+            let op = reader.byte()?;
+
+            use Instruction::*;
+            let instruction = match op {
+                OP_TRUE => (True, 0),
+                OP_FALSE => (False, 0),
+
+                OP_ARGUMENT => {
+                    let index = reader.varint()?;
+                    let identifier = identifiers
+                        .get(index)
+                        .ok_or_else(|| format!("identifier index {index} is out of bounds"))?;
+
+                    referenced[index] = true;
+                    (Argument(identifier.clone()), 0)
+                }
+
+                OP_NOT => {
+                    let (x, depth) = pop(&mut stack)?;
+                    (Not(Box::new(x)), depth + 1)
+                }
+
+                OP_AND | OP_OR | OP_XOR | OP_CONDITIONAL | OP_BICONDITIONAL | OP_EQUALS => {
+                    let (r, r_depth) = pop(&mut stack)?;
+                    let (l, l_depth) = pop(&mut stack)?;
+                    let (l, r) = (Box::new(l), Box::new(r));
+
+                    let instruction = match op {
+                        OP_AND => And(l, r),
+                        OP_OR => Or(l, r),
+                        OP_XOR => Xor(l, r),
+                        OP_CONDITIONAL => Conditional(l, r),
+                        OP_BICONDITIONAL => Biconditional(l, r),
+                        _ => Equals(l, r),
+                    };
+
+                    (instruction, l_depth.max(r_depth) + 1)
+                }
+
+                _ => return Err(format!("invalid opcode 0x{op:02x}")),
+            };
+
+            if instruction.1 > MAX_DEPTH {
+                return Err(format!(
+                    "the operators are nested more than {MAX_DEPTH} levels deep"
+                ));
+            }
+
+            stack.push(instruction);
+        }
+
+        if reader.remaining() > 0 {
+            return Err(format!("{} trailing bytes", reader.remaining()));
+        }
+
+        if let Some(index) = referenced.iter().position(|r| !r) {
+            return Err(format!(
+                "the identifier `{}` is never referenced",
+                identifiers[index]
+            ));
+        }
+
+        let (instruction, _) = pop(&mut stack)?;
+        if !stack.is_empty() {
+            return Err(format!(
+                "the opcodes leave {} dangling operands",
+                stack.len()
+            ));
+        }
+
+        Ok(instruction)
+    }
+}
+
+fn pop(stack: &mut Vec<(Instruction, usize)>) -> Result<(Instruction, usize), String> {
+    stack
+        .pop()
+        .ok_or_else(|| "an opcode is missing its operands".to_string())
+}
+
+fn write_varint(bytes: &mut Vec<u8>, mut value: usize) {
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+
+        if value == 0 {
+            bytes.push(byte);
+            return;
+        }
+
+        bytes.push(byte | 0x80);
+    }
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn remaining(&self) -> usize {
+        self.bytes.len() - self.position
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], String> {
+        if len > self.remaining() {
+            return Err("unexpected end of input".to_string());
+        }
+
+        let bytes = &self.bytes[self.position..self.position + len];
+        self.position += len;
+
+        Ok(bytes)
+    }
+
+    fn byte(&mut self) -> Result<u8, String> {
+        self.take(1).map(|b| b[0])
+    }
+
+    fn varint(&mut self) -> Result<usize, String> {
+        let mut value = 0usize;
+        let mut shift = 0;
+
+        loop {
+            let byte = self.byte()?;
+            let bits = (byte & 0x7f) as usize;
+
+            if shift >= usize::BITS || (bits << shift) >> shift != bits {
+                return Err("varint overflow".to_string());
+            }
+
+            value |= bits << shift;
+            shift += 7;
+
+            if byte & 0x80 == 0 {
+                // a canonical varint never ends with a zero continuation byte
+                if byte == 0 && shift > 7 {
+                    return Err("non-canonical varint".to_string());
+                }
+
+                return Ok(value);
+            }
+        }
+    }
+}
@@ -0,0 +1,156 @@
+use super::*;
+
+#[test]
+fn roundtrip() {
+    for expr in [
+        "a",
+        "!a",
+        "!(a + b) = !c -> x",
+        "(a <-> 'flag one') ^ (\"x\" v !a)",
+        "a ^ a ^ a ^ a",
+    ] {
+        let instruction = Instruction::try_from(expr).unwrap();
+        let bytes = instruction.to_bytes().unwrap();
+
+        assert_eq!(instruction, Instruction::from_bytes(&bytes).unwrap());
+    }
+
+    let instruction = Instruction::And(
+        Box::new(Instruction::True),
+        Box::new(Instruction::Not(Box::new(Instruction::False))),
+    );
+
+    assert_eq!(
+        instruction,
+        Instruction::from_bytes(&instruction.to_bytes().unwrap()).unwrap()
+    );
+}
+
+#[test]
+fn encode_identifiers_once() {
+    let instruction = Instruction::try_from("abc ^ abc ^ abc").unwrap();
+    let expected = vec![
+        b'T',
+        b'F',
+        VERSION,
+        1,
+        3,
+        b'a',
+        b'b',
+        b'c',
+        5,
+        OP_ARGUMENT,
+        0,
+        OP_ARGUMENT,
+        0,
+        OP_AND,
+        OP_ARGUMENT,
+        0,
+        OP_AND,
+    ];
+
+    assert_eq!(expected, instruction.to_bytes().unwrap());
+}
+
+#[test]
+fn reject_malformed_input() {
+    let valid = Instruction::try_from("a v b").unwrap().to_bytes().unwrap();
+
+    let cases: Vec<Vec<u8>> = vec![
+        // empty
+        vec![],
+        // invalid magic
+        vec![b'X', b'F', VERSION, 0, 1, OP_TRUE],
+        // unsupported version
+        vec![b'T', b'F', VERSION + 1, 0, 1, OP_TRUE],
+        // truncated
+        valid[..valid.len() - 1].to_vec(),
+        // trailing bytes
+        [valid.as_slice(), &[0]].concat(),
+        // no opcodes
+        vec![b'T', b'F', VERSION, 0, 0],
+        // invalid opcode
+        vec![b'T', b'F', VERSION, 0, 1, 0xff],
+        // missing operands
+        vec![b'T', b'F', VERSION, 0, 2, OP_TRUE, OP_AND],
+        // dangling operands
+        vec![b'T', b'F', VERSION, 0, 2, OP_TRUE, OP_TRUE],
+        // identifier out of bounds
+        vec![b'T', b'F', VERSION, 1, 1, b'a', 1, OP_ARGUMENT, 1],
+        // unreferenced identifier
+        vec![b'T', b'F', VERSION, 1, 1, b'a', 1, OP_TRUE],
+        // duplicated identifier
+        vec![
+            b'T',
+            b'F',
+            VERSION,
+            2,
+            1,
+            b'a',
+            1,
+            b'a',
+            3,
+            OP_ARGUMENT,
+            0,
+            OP_ARGUMENT,
+            1,
+            OP_OR,
+        ],
+        // invalid UTF-8
+        vec![b'T', b'F', VERSION, 1, 1, 0xff, 1, OP_ARGUMENT, 0],
+        // identifier longer than the input
+        vec![b'T', b'F', VERSION, 1, 0x7f, b'a'],
+        // non-canonical varint
+        vec![b'T', b'F', VERSION, 0x80, 0x00, 1, OP_TRUE],
+        // varint overflow
+        [&[b'T', b'F', VERSION][..], &[0xff; 10], &[0x01, 1, OP_TRUE]].concat(),
+    ];
+
+    for bytes in cases {
+        assert!(
+            Instruction::from_bytes(&bytes).is_err(),
+            "{bytes:?} should be rejected"
+        );
+    }
+}
+
+#[test]
+fn reject_deep_nesting() {
+    let nested = |depth: usize| {
+        let mut bytes = vec![b'T', b'F', VERSION, 1, 1, b'a'];
+        write_varint(&mut bytes, depth + 1);
+        bytes.extend([OP_ARGUMENT, 0]);
+        bytes.extend(std::iter::repeat_n(OP_NOT, depth));
+        bytes
+    };
+
+    let instruction = Instruction::from_bytes(&nested(MAX_DEPTH)).unwrap();
+    assert_eq!(nested(MAX_DEPTH), instruction.to_bytes().unwrap());
+
+    assert!(Instruction::from_bytes(&nested(MAX_DEPTH + 1)).is_err());
+    assert!(Instruction::from_bytes(&nested(1_000_000)).is_err());
+}
+
+#[test]
+fn roundtrip_deep_chains() {
+    let chain = |terms: usize| {
+        let expr = (0..terms)
+            .map(|i| format!("x{i}"))
+            .collect::<Vec<_>>()
+            .join(" ^ ");
+
+        Instruction::try_from(expr.as_str()).unwrap()
+    };
+
+    // a chain of `n` terms nests `n - 1` operators
+    let instruction = chain(MAX_DEPTH + 1);
+    let bytes = instruction.to_bytes().unwrap();
+    assert_eq!(instruction, Instruction::from_bytes(&bytes).unwrap());
+
+    assert_eq!(
+        Err(format!(
+            "the operators are nested more than {MAX_DEPTH} levels deep"
+        )),
+        chain(1100).to_bytes()
+    );
+}
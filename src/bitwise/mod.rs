@@ -0,0 +1,295 @@
+use super::*;
+use bytecode::Program;
+use std::io;
+use traverser::Arguments;
+
+#[cfg(test)]
+mod tests;
+
+/// Number of assignments evaluated at once.
+pub const LANES: usize = u64::BITS as usize;
+
+/// Lane patterns of the six least significant bits of the row index.
+const PATTERNS: [u64; 6] = [
+    0xaaaa_aaaa_aaaa_aaaa,
+    0xcccc_cccc_cccc_cccc,
+    0xf0f0_f0f0_f0f0_f0f0,
+    0xff00_ff00_ff00_ff00,
+    0xffff_0000_ffff_0000,
+    0xffff_ffff_0000_0000,
+];
+
+/// Maximum number of arguments of a truth table.
+pub const MAX_ARGUMENTS: usize = 32;
+
+/// The result column of a truth table, packed as one bit per row.
+///
+/// Row `i` assigns to the argument of column `j` the bit `n - 1 - j` of `i`, where `n` is the
+/// number of arguments. The rows are then in ascending binary order, the first column being the
+/// most significant bit.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Truth {
+    pub arguments: Vec<String>,
+    pub words: Vec<u64>,
+}
+
+/// Evaluator of the words of a truth table, each word packing [`LANES`] consecutive rows.
+///
+/// Several instructions can be evaluated over the same arguments, producing one word each.
+pub struct Words<'a> {
+    arguments: Vec<&'a str>,
+    programs: Vec<Program<'a>>,
+    inputs: Vec<u64>,
+    stack: Vec<u64>,
+    results: Vec<u64>,
+    valid: u64,
+}
+
+impl<'a> Words<'a> {
+    pub fn new(instruction: &'a Instruction, arguments: Vec<&'a str>) -> Result<Self, String> {
+        Self::with_instructions(&[instruction], arguments)
+    }
+
+    pub fn with_instructions(
+        instructions: &[&'a Instruction],
+        arguments: Vec<&'a str>,
+    ) -> Result<Self, String> {
+        let rows = 1usize << arguments.len();
+        let valid = if rows < LANES { (1u64 << rows) - 1 } else { !0 };
+
+        let programs = instructions
+            .iter()
+            .map(|i| Program::with_arguments(i, arguments.clone()))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self {
+            inputs: vec![0; arguments.len()],
+            results: vec![0; programs.len()],
+            arguments,
+            programs,
+            stack: Vec::new(),
+            valid,
+        })
+    }
+
+    pub fn arguments(&self) -> &[&'a str] {
+        &self.arguments
+    }
+
+    /// Evaluate the rows `LANES * w..LANES * (w + 1)` of every instruction, the bit `k` being the
+    /// row `LANES * w + k`.
+    pub fn words(&mut self, w: usize) -> &[u64] {
+        let n = self.inputs.len();
+
+        for (j, input) in self.inputs.iter_mut().enumerate() {
+            let bit = n - 1 - j;
+            *input = if bit < PATTERNS.len() {
+                PATTERNS[bit]
+            } else if w >> (bit - PATTERNS.len()) & 1 == 1 {
+                !0
+            } else {
+                0
+            };
+        }
+
+        for (program, result) in self.programs.iter().zip(self.results.iter_mut()) {
+            *result = program
+                .run(&self.inputs, &mut self.stack)
+                .expect("the programs have one slot per input")
+                & self.valid;
+        }
+
+        &self.results
+    }
+
+    /// Evaluate the rows `LANES * w..LANES * (w + 1)` of the first instruction.
+    pub fn word(&mut self, w: usize) -> u64 {
+        self.words(w)[0]
+    }
+}
+
+/// Truth table evaluator that packs [`LANES`] assignments into every word.
+///
+/// The instruction is compiled once into a [`Program`], which then evaluates every node once per
+/// word with bitwise operations, instead of once per assignment.
+pub struct BitEvaluator;
+
+impl BitEvaluator {
+    /// The truth table of the instruction over the arguments, in the order given, which may
+    /// include arguments the instruction does not depend on.
+    pub fn truth<'a>(
+        instruction: &'a Instruction,
+        arguments: Vec<&'a str>,
+    ) -> Result<Truth, String> {
+        let n = arguments.len();
+
+        if n > MAX_ARGUMENTS {
+            return Err(format!(
+                "the instruction has {n} arguments; the maximum for a truth table is {MAX_ARGUMENTS}"
+            ));
+        }
+
+        let mut words = Words::new(instruction, arguments)?;
+        let len = (1usize << n).div_ceil(LANES);
+        let bits = (0..len).map(|w| words.word(w)).collect();
+
+        let arguments = words.arguments().iter().map(|a| a.to_string()).collect();
+
+        Ok(Truth {
+            arguments,
+            words: bits,
+        })
+    }
+}
+
+/// Lazy iterator over the rows of a truth table.
+///
+/// The rows are yielded in the same ascending binary order of [`Instruction::evaluate`], but only
+/// the word of [`LANES`] rows around the current one is evaluated and kept in memory. Skipping rows
+/// with [`Iterator::nth`] or [`Iterator::skip`] does not evaluate the skipped words.
+pub struct Rows<'a> {
+    words: Words<'a>,
+    row: usize,
+    rows: usize,
+    current: Option<(usize, u64)>,
+}
+
+impl<'a> Rows<'a> {
+    pub fn new(instruction: &'a Instruction) -> Result<Self, String> {
+        let arguments = Arguments::sorted(instruction);
+        let n = arguments.len();
+
+        if n >= usize::BITS as usize {
+            return Err(format!(
+                "the instruction has too many arguments ({n}) to be enumerated"
+            ));
+        }
+
+        Ok(Self {
+            words: Words::new(instruction, arguments)?,
+            row: 0,
+            rows: 1 << n,
+            current: None,
+        })
+    }
+
+    /// Names of the arguments, in the order of the values of every row.
+    pub fn arguments(&self) -> &[&'a str] {
+        self.words.arguments()
+    }
+
+    fn bits(&mut self, w: usize) -> u64 {
+        match self.current {
+            Some((current, bits)) if current == w => bits,
+            _ => {
+                let bits = self.words.word(w);
+                self.current.replace((w, bits));
+                bits
+            }
+        }
+    }
+
+    fn evaluation(&self, row: usize, result: bool) -> Evaluation<'a> {
+        let arguments = self.words.arguments();
+        let n = arguments.len();
+        let values = arguments
+            .iter()
+            .enumerate()
+            .map(|(j, arg)| (*arg, row >> (n - 1 - j) & 1 == 1))
+            .collect();
+
+        Evaluation { values, result }
+    }
+
+    /// Advance to the next row that evaluates to `result`, skipping whole words that contain no
+    /// such row.
+    pub fn next_with_result(&mut self, result: bool) -> Option<Evaluation<'a>> {
+        while self.row < self.rows {
+            let w = self.row / LANES;
+            let mut bits = self.bits(w);
+            if !result {
+                bits = !bits;
+            }
+
+            // discard the rows of the word that were already consumed
+            bits &= !0 << (self.row % LANES);
+
+            if bits == 0 {
+                self.row = (w + 1) * LANES;
+                continue;
+            }
+
+            let row = w * LANES + bits.trailing_zeros() as usize;
+            if row >= self.rows {
+                break;
+            }
+
+            self.row = row + 1;
+            return Some(self.evaluation(row, result));
+        }
+
+        self.row = self.rows;
+        None
+    }
+
+    /// Write the remaining rows as CSV, preceded by a header with the argument names and `eval`.
+    pub fn write_to<W>(self, mut writer: W) -> io::Result<()>
+    where
+        W: io::Write,
+    {
+        let header = self
+            .arguments()
+            .iter()
+            .map(|a| csv_field(a))
+            .chain(iter::once("eval".to_string()))
+            .collect::<Vec<_>>()
+            .join(",");
+
+        writeln!(writer, "{header}")?;
+
+        for ev in self {
+            for (_, v) in ev.iter() {
+                write!(writer, "{},", u8::from(*v))?;
+            }
+            writeln!(writer, "{}", u8::from(ev.result()))?;
+        }
+
+        writer.flush()
+    }
+}
+
+impl<'a> Iterator for Rows<'a> {
+    type Item = Evaluation<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.row >= self.rows {
+            return None;
+        }
+
+        let row = self.row;
+        let bits = self.bits(row / LANES);
+        self.row += 1;
+
+        Some(self.evaluation(row, bits >> (row % LANES) & 1 == 1))
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.row = self.row.saturating_add(n).min(self.rows);
+        self.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.rows - self.row;
+        (len, Some(len))
+    }
+}
+
+impl<'a> ExactSizeIterator for Rows<'a> {}
+
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
@@ -0,0 +1,120 @@
+use super::*;
+
+#[test]
+fn evaluate_as_traverser() {
+    for expr in [
+        "a",
+        "!a",
+        "a v !a",
+        "a ^ !a",
+        "!(a + b) = !c -> x",
+        "(a <-> b) ^ (c v (d -> e)) + f",
+        "(a ^ b ^ c ^ d ^ e ^ f ^ g) v (h + i)",
+    ] {
+        let instruction = Instruction::try_from(expr).unwrap();
+        let expected = traverser::Evaluator::run(&instruction)
+            .map(Evaluation::into_table)
+            .unwrap();
+
+        assert_eq!(expected, instruction.evaluate().unwrap(), "{expr}");
+    }
+}
+
+#[test]
+fn evaluate_constants() {
+    let table = Instruction::True.evaluate().unwrap();
+
+    assert_eq!(vec!["eval".to_string()], table.header);
+    assert_eq!(vec![vec![true]], table.rows);
+}
+
+#[test]
+fn evaluate_many_arguments() {
+    let expr = (0..20)
+        .map(|i| format!("x{i:02}"))
+        .collect::<Vec<_>>()
+        .join(" ^ ");
+
+    let instruction = Instruction::try_from(expr.as_str()).unwrap();
+    let truth = BitEvaluator::truth(&instruction, Arguments::sorted(&instruction)).unwrap();
+
+    assert_eq!((1 << 20) / LANES, truth.words.len());
+    assert_eq!(1 << 63, truth.words[truth.words.len() - 1]);
+    assert_eq!(1, truth.words.iter().map(|w| w.count_ones()).sum::<u32>());
+}
+
+#[test]
+fn rows_as_table() {
+    let instruction = Instruction::try_from("(a <-> b) ^ (c v (d -> e)) + f").unwrap();
+    let table = instruction.evaluate().unwrap();
+    let rows = instruction
+        .rows()
+        .unwrap()
+        .map(|ev| {
+            ev.iter()
+                .map(|(_, v)| *v)
+                .chain(iter::once(ev.result()))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(table.rows, rows);
+}
+
+#[test]
+fn rows_skip() {
+    let instruction = Instruction::try_from("a ^ b ^ c ^ d ^ e ^ f ^ g ^ h").unwrap();
+    let mut rows = instruction.rows().unwrap();
+
+    assert_eq!(256, rows.len());
+
+    let ev = rows.nth(255).unwrap();
+    assert!(ev.result());
+    assert!(ev.iter().all(|(_, v)| *v));
+    assert!(rows.next().is_none());
+
+    let rows = instruction.rows().unwrap().skip(100).take(10).count();
+    assert_eq!(10, rows);
+}
+
+#[test]
+fn rows_next_with_result() {
+    let expr = (0..20)
+        .map(|i| format!("x{i:02}"))
+        .collect::<Vec<_>>()
+        .join(" ^ ");
+
+    let instruction = Instruction::try_from(expr.as_str()).unwrap();
+    let mut rows = instruction.rows().unwrap();
+
+    let ev = rows.next_with_result(true).unwrap();
+    assert!(ev.result());
+    assert!(ev.iter().all(|(_, v)| *v));
+    assert!(rows.next_with_result(true).is_none());
+
+    let mut rows = instruction.rows().unwrap();
+    let first = rows.next_with_result(false).unwrap();
+    let second = rows.next_with_result(false).unwrap();
+    assert!(first.iter().all(|(_, v)| !*v));
+    assert_eq!(Some(&("x19", true)), second.last());
+}
+
+#[test]
+fn rows_write_to() {
+    let instruction = Instruction::try_from("!a v 'b,c'").unwrap();
+    let mut csv = vec![];
+
+    instruction.rows().unwrap().write_to(&mut csv).unwrap();
+
+    let expected = "a,\"b,c\",eval\n0,0,1\n0,1,1\n1,0,0\n1,1,1\n";
+    assert_eq!(expected, String::from_utf8(csv).unwrap());
+}
+
+#[test]
+fn truth_over_more_arguments_than_used() {
+    let instruction = Instruction::try_from("a ^ !a v b").unwrap();
+    let truth = BitEvaluator::truth(&instruction, vec!["a", "b", "c"]).unwrap();
+
+    assert_eq!(vec!["a", "b", "c"], truth.arguments);
+    assert_eq!(vec![0b1100_1100], truth.words);
+}
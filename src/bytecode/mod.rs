@@ -0,0 +1,193 @@
+use super::*;
+use std::ops::{BitAnd, BitOr, BitXor, Not};
+use traverser::Arguments;
+
+#[cfg(test)]
+mod tests;
+
+/// Value manipulated by the stack machine.
+///
+/// A `bool` evaluates a single assignment, while a `u64` evaluates one assignment per bit.
+pub trait Word:
+    Copy + Not<Output = Self> + BitAnd<Output = Self> + BitOr<Output = Self> + BitXor<Output = Self>
+{
+    const TRUE: Self;
+    const FALSE: Self;
+}
+
+impl Word for bool {
+    const TRUE: Self = true;
+    const FALSE: Self = false;
+}
+
+impl Word for u64 {
+    const TRUE: Self = !0;
+    const FALSE: Self = 0;
+}
+
+/// Operation of the stack machine.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Op {
+    True,
+    False,
+    /// Push the input of the slot.
+    Load(usize),
+    Not,
+    And,
+    Or,
+    Xor,
+    Conditional,
+    /// Shared by biconditionals and equalities.
+    Equals,
+}
+
+/// An instruction lowered to a linear sequence of stack operations.
+///
+/// Every argument is resolved to an integer slot when the program is compiled, so evaluating it
+/// is a single loop over the operations with no lookups by name. The inputs of a run are indexed
+/// by slot, in the order of [`Program::arguments`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Program<'a> {
+    arguments: Vec<&'a str>,
+    ops: Vec<Op>,
+    depth: usize,
+}
+
+impl<'a> Program<'a> {
+    /// Compile the instruction with its arguments sorted alphabetically, as the columns of a truth
+    /// table.
+    pub fn compile(instruction: &'a Instruction) -> Self {
+        Self::with_arguments(instruction, Arguments::sorted(instruction))
+            .expect("the arguments were extracted from the instruction")
+    }
+
+    /// Compile the instruction with the provided slot order.
+    pub fn with_arguments(
+        instruction: &'a Instruction,
+        arguments: Vec<&'a str>,
+    ) -> Result<Self, String> {
+        let mut program = Self {
+            arguments,
+            ops: vec![],
+            depth: 0,
+        };
+
+        program.lower(instruction, 0)?;
+
+        Ok(program)
+    }
+
+    /// Names of the arguments, indexed by slot.
+    pub fn arguments(&self) -> &[&'a str] {
+        &self.arguments
+    }
+
+    pub fn ops(&self) -> &[Op] {
+        &self.ops
+    }
+
+    fn lower(&mut self, instruction: &Instruction, height: usize) -> Result<(), String> {
+        self.depth = self.depth.max(height + 1);
+
+        use Instruction::*;
+        let (op, l, r) = match instruction {
+            True => (Op::True, None, None),
+            False => (Op::False, None, None),
+
+            Argument(a) => {
+                let slot = self
+                    .arguments
+                    .iter()
+                    .position(|arg| arg == a)
+                    .ok_or_else(|| format!("the argument `{a}` has no slot"))?;
+
+                (Op::Load(slot), None, None)
+            }
+
+            Not(x) => (Op::Not, Some(x), None),
+            And(l, r) => (Op::And, Some(l), Some(r)),
+            Or(l, r) => (Op::Or, Some(l), Some(r)),
+            Xor(l, r) => (Op::Xor, Some(l), Some(r)),
+            Conditional(l, r) => (Op::Conditional, Some(l), Some(r)),
+            Biconditional(l, r) | Equals(l, r) => (Op::Equals, Some(l), Some(r)),
+        };
+
+        if let Some(l) = l {
+            self.lower(l, height)?;
+        }
+
+        if let Some(r) = r {
+            self.lower(r, height + 1)?;
+        }
+
+        self.ops.push(op);
+
+        Ok(())
+    }
+
+    /// Evaluate the program, resolving the input of every slot from the assignment.
+    pub fn eval_with<A>(&self, assignment: &A) -> Result<bool, String>
+    where
+        A: Assignment + ?Sized,
+    {
+        let inputs = self.resolve(assignment)?;
+        let mut stack = Vec::with_capacity(self.depth);
+
+        self.run(&inputs, &mut stack)
+    }
+
+    /// Resolve the inputs of the slots from the assignment.
+    pub fn resolve<A>(&self, assignment: &A) -> Result<Vec<bool>, String>
+    where
+        A: Assignment + ?Sized,
+    {
+        self.arguments
+            .iter()
+            .map(|arg| {
+                assignment
+                    .value(arg)
+                    .ok_or_else(|| format!("the argument `{arg}` is unbound"))
+            })
+            .collect()
+    }
+
+    /// Evaluate the program, reusing the provided stack.
+    ///
+    /// Fails unless the inputs contain one word per slot.
+    pub fn run<W: Word>(&self, inputs: &[W], stack: &mut Vec<W>) -> Result<W, String> {
+        if inputs.len() != self.arguments.len() {
+            return Err(format!(
+                "the program expects {} inputs, but {} were provided",
+                self.arguments.len(),
+                inputs.len()
+            ));
+        }
+
+        stack.clear();
+
+        for op in &self.ops {
+            let word = match op {
+                Op::True => W::TRUE,
+                Op::False => W::FALSE,
+                Op::Load(slot) => inputs[*slot],
+                Op::Not => !stack.pop().expect("the program is well-formed"),
+                _ => {
+                    let r = stack.pop().expect("the program is well-formed");
+                    let l = stack.pop().expect("the program is well-formed");
+
+                    match op {
+                        Op::And => l & r,
+                        Op::Or => l | r,
+                        Op::Xor => l ^ r,
+                        Op::Conditional => !l | r,
+                        _ => !(l ^ r),
+                    }
+                }
+            };
+
+            stack.push(word);
+        }
+
+        Ok(stack.pop().expect("the program is well-formed"))
+    }
+}
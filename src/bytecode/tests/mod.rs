@@ -0,0 +1,51 @@
+use super::*;
+
+#[test]
+fn compile_postfix() {
+    let instruction = Instruction::try_from("!b ^ (a <-> c)").unwrap();
+    let program = instruction.bytecode();
+
+    assert_eq!(&["a", "b", "c"], program.arguments());
+    assert_eq!(
+        &[
+            Op::Load(1),
+            Op::Not,
+            Op::Load(0),
+            Op::Load(2),
+            Op::Equals,
+            Op::And
+        ],
+        program.ops()
+    );
+}
+
+#[test]
+fn eval_as_truth_table() {
+    let instruction = Instruction::try_from("!(a + b) = !c -> (x v a)").unwrap();
+    let program = instruction.bytecode();
+    let table = instruction.evaluate().unwrap();
+    let mut stack = Vec::new();
+
+    for row in table.rows {
+        let (eval, inputs) = row.split_last().unwrap();
+
+        assert_eq!(*eval, program.run(inputs, &mut stack).unwrap());
+    }
+}
+
+#[test]
+fn run_invalid_inputs() {
+    let instruction = Instruction::try_from("a v b").unwrap();
+
+    let program = instruction.bytecode();
+
+    assert!(program.run(&[true], &mut Vec::new()).is_err());
+    assert!(program.run(&[!0u64; 3], &mut Vec::new()).is_err());
+}
+
+#[test]
+fn compile_unknown_argument() {
+    let instruction = Instruction::try_from("a v b").unwrap();
+
+    assert!(Program::with_arguments(&instruction, vec!["a"]).is_err());
+}
@@ -0,0 +1,349 @@
+use super::*;
+use std::{collections::HashMap, fmt::Write as _};
+
+#[cfg(test)]
+mod tests;
+
+const MARGIN: usize = 20;
+const COLUMN_WIDTH: usize = 120;
+const ROW_HEIGHT: usize = 70;
+const GATE_WIDTH: usize = 50;
+const GATE_HEIGHT: usize = 40;
+const BUBBLE: usize = 8;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Gate {
+    Input(String),
+    Constant(bool),
+    Not(usize),
+    And(usize, usize),
+    Or(usize, usize),
+    Xor(usize, usize),
+    Nand(usize, usize),
+    Nor(usize, usize),
+    Xnor(usize, usize),
+}
+
+impl Gate {
+    fn operands(&self) -> Vec<usize> {
+        match self {
+            Gate::Input(_) | Gate::Constant(_) => vec![],
+            Gate::Not(x) => vec![*x],
+            Gate::And(l, r)
+            | Gate::Or(l, r)
+            | Gate::Xor(l, r)
+            | Gate::Nand(l, r)
+            | Gate::Nor(l, r)
+            | Gate::Xnor(l, r) => vec![*l, *r],
+        }
+    }
+
+    const fn class(&self) -> &'static str {
+        match self {
+            Gate::Input(_) => "input",
+            Gate::Constant(_) => "constant",
+            Gate::Not(_) => "not",
+            Gate::And(_, _) => "and",
+            Gate::Or(_, _) => "or",
+            Gate::Xor(_, _) => "xor",
+            Gate::Nand(_, _) => "nand",
+            Gate::Nor(_, _) => "nor",
+            Gate::Xnor(_, _) => "xnor",
+        }
+    }
+}
+
+/// Gate-level schematic of an instruction, rendered as SVG.
+///
+/// The instruction is lowered to AND, OR, XOR, NOT and their negated forms; conditionals become
+/// `!l v r` and biconditionals become XNOR gates. Structurally equal subexpressions are lowered
+/// to a single gate, so they are drawn only once.
+///
+/// Every gate is placed in the column of its depth, counting from the inputs on the left, so the
+/// output of the circuit is the rightmost gate.
+#[derive(Debug, Default)]
+pub struct Circuit {
+    gates: Vec<Gate>,
+    memo: HashMap<Instruction, usize>,
+}
+
+impl Circuit {
+    pub fn render(instruction: &Instruction) -> String {
+        let mut circuit = Self::default();
+        let output = circuit.lower(instruction);
+
+        circuit.svg(output)
+    }
+
+    fn push(&mut self, instruction: &Instruction, gate: Gate) -> usize {
+        let id = match self.gates.iter().position(|g| g == &gate) {
+            Some(id) => id,
+            None => {
+                self.gates.push(gate);
+                self.gates.len() - 1
+            }
+        };
+
+        self.memo.insert(instruction.clone(), id);
+        id
+    }
+
+    fn lower(&mut self, instruction: &Instruction) -> usize {
+        if let Some(id) = self.memo.get(instruction) {
+            return *id;
+        }
+
+        use Instruction::*;
+        let gate = match instruction {
+            True => Gate::Constant(true),
+            False => Gate::Constant(false),
+            Argument(a) => Gate::Input(a.clone()),
+
+            Not(x) => match x.as_ref() {
+                And(l, r) => Gate::Nand(self.lower(l), self.lower(r)),
+                Or(l, r) => Gate::Nor(self.lower(l), self.lower(r)),
+                Xor(l, r) => Gate::Xnor(self.lower(l), self.lower(r)),
+                _ => Gate::Not(self.lower(x)),
+            },
+
+            And(l, r) => Gate::And(self.lower(l), self.lower(r)),
+            Or(l, r) => Gate::Or(self.lower(l), self.lower(r)),
+            Xor(l, r) => Gate::Xor(self.lower(l), self.lower(r)),
+
+            Conditional(l, r) => {
+                let l = Not(l.clone());
+                let id = self.lower(&Or(Box::new(l), r.clone()));
+
+                self.memo.insert(instruction.clone(), id);
+                return id;
+            }
+
+            Biconditional(l, r) | Equals(l, r) => Gate::Xnor(self.lower(l), self.lower(r)),
+        };
+
+        self.push(instruction, gate)
+    }
+
+    fn svg(&self, output: usize) -> String {
+        // gates are pushed after their operands, so a single pass computes the depth
+        let mut levels = vec![0usize; self.gates.len()];
+        for (id, gate) in self.gates.iter().enumerate() {
+            levels[id] = gate
+                .operands()
+                .into_iter()
+                .map(|o| levels[o] + 1)
+                .max()
+                .unwrap_or(0);
+        }
+
+        let depth = levels[output];
+        let mut columns = vec![vec![]; depth + 1];
+        for (id, level) in levels.iter().enumerate() {
+            columns[*level].push(id);
+        }
+
+        let rows = columns.iter().map(Vec::len).max().unwrap_or(1);
+        let width = 2 * MARGIN + (depth + 1) * COLUMN_WIDTH + 40;
+        let height = 2 * MARGIN + rows * ROW_HEIGHT;
+
+        let mut positions = HashMap::with_capacity(self.gates.len());
+        for (level, column) in columns.iter().enumerate() {
+            let offset = (rows - column.len()) * ROW_HEIGHT / 2;
+            for (row, id) in column.iter().enumerate() {
+                let x = MARGIN + level * COLUMN_WIDTH;
+                let y = MARGIN + offset + row * ROW_HEIGHT + ROW_HEIGHT / 2;
+                positions.insert(*id, (x, y));
+            }
+        }
+
+        let mut svg = String::new();
+        let w = &mut svg;
+
+        writeln!(
+            w,
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" viewBox=\"0 0 {width} {height}\">"
+        )
+        .expect("writing to a string is infallible");
+        writeln!(
+            w,
+            "  <g fill=\"none\" stroke=\"black\" stroke-width=\"2\" font-family=\"monospace\" font-size=\"14\">"
+        )
+        .expect("writing to a string is infallible");
+
+        for column in &columns {
+            for id in column {
+                let gate = &self.gates[*id];
+                let (x, y) = positions[id];
+
+                self.gate(w, gate, x, y);
+
+                let operands = gate.operands();
+                let pins = Self::input_pins(operands.len(), y);
+                for (pin, (operand, pin_y)) in operands.iter().zip(pins).enumerate() {
+                    let (sx, sy) = positions[operand];
+                    let sx = sx + self.output_offset(*operand);
+                    let mx = x - 15 - 5 * pin;
+
+                    writeln!(
+                        w,
+                        "    <polyline points=\"{sx},{sy} {mx},{sy} {mx},{pin_y} {x},{pin_y}\"/>"
+                    )
+                    .expect("writing to a string is infallible");
+                }
+            }
+        }
+
+        let (x, y) = positions[&output];
+        let x = x + self.output_offset(output);
+        writeln!(
+            w,
+            "    <line x1=\"{x}\" y1=\"{y}\" x2=\"{}\" y2=\"{y}\"/>",
+            x + 20
+        )
+        .expect("writing to a string is infallible");
+        writeln!(
+            w,
+            "    <text x=\"{}\" y=\"{}\" stroke=\"none\" fill=\"black\">out</text>",
+            x + 24,
+            y + 5
+        )
+        .expect("writing to a string is infallible");
+
+        writeln!(w, "  </g>").expect("writing to a string is infallible");
+        writeln!(w, "</svg>").expect("writing to a string is infallible");
+
+        svg
+    }
+
+    fn input_pins(count: usize, y: usize) -> Vec<usize> {
+        match count {
+            1 => vec![y],
+            2 => vec![y - GATE_HEIGHT / 4, y + GATE_HEIGHT / 4],
+            _ => vec![],
+        }
+    }
+
+    fn output_offset(&self, id: usize) -> usize {
+        match &self.gates[id] {
+            Gate::Input(_) | Gate::Constant(_) => GATE_WIDTH,
+            Gate::Not(_) | Gate::Nand(_, _) | Gate::Nor(_, _) | Gate::Xnor(_, _) => {
+                GATE_WIDTH + BUBBLE
+            }
+            Gate::And(_, _) | Gate::Or(_, _) | Gate::Xor(_, _) => GATE_WIDTH,
+        }
+    }
+
+    fn gate(&self, w: &mut String, gate: &Gate, x: usize, y: usize) {
+        let top = y - GATE_HEIGHT / 2;
+        let class = gate.class();
+
+        writeln!(w, "    <g class=\"{class}\">").expect("writing to a string is infallible");
+
+        match gate {
+            Gate::Input(name) => {
+                writeln!(
+                    w,
+                    "      <text x=\"{}\" y=\"{}\" text-anchor=\"end\" stroke=\"none\" fill=\"black\">{}</text>",
+                    x + GATE_WIDTH - 12,
+                    y + 5,
+                    escape(name)
+                )
+                .expect("writing to a string is infallible");
+                writeln!(
+                    w,
+                    "      <circle cx=\"{}\" cy=\"{y}\" r=\"3\" fill=\"black\"/>",
+                    x + GATE_WIDTH - 6
+                )
+                .expect("writing to a string is infallible");
+                writeln!(
+                    w,
+                    "      <line x1=\"{}\" y1=\"{y}\" x2=\"{}\" y2=\"{y}\"/>",
+                    x + GATE_WIDTH - 6,
+                    x + GATE_WIDTH
+                )
+                .expect("writing to a string is infallible");
+            }
+
+            Gate::Constant(value) => {
+                writeln!(
+                    w,
+                    "      <text x=\"{}\" y=\"{}\" text-anchor=\"end\" stroke=\"none\" fill=\"black\">{}</text>",
+                    x + GATE_WIDTH - 6,
+                    y + 5,
+                    u8::from(*value)
+                )
+                .expect("writing to a string is infallible");
+            }
+
+            Gate::Not(_) => {
+                writeln!(
+                    w,
+                    "      <path d=\"M {x} {top} L {} {y} L {x} {} Z\"/>",
+                    x + GATE_WIDTH,
+                    top + GATE_HEIGHT
+                )
+                .expect("writing to a string is infallible");
+            }
+
+            Gate::And(_, _) | Gate::Nand(_, _) => {
+                writeln!(
+                    w,
+                    "      <path d=\"M {x} {top} H {} A {r} {r} 0 0 1 {} {} H {x} Z\"/>",
+                    x + GATE_WIDTH - GATE_HEIGHT / 2,
+                    x + GATE_WIDTH - GATE_HEIGHT / 2,
+                    top + GATE_HEIGHT,
+                    r = GATE_HEIGHT / 2
+                )
+                .expect("writing to a string is infallible");
+            }
+
+            Gate::Or(_, _) | Gate::Nor(_, _) | Gate::Xor(_, _) | Gate::Xnor(_, _) => {
+                writeln!(
+                    w,
+                    "      <path d=\"M {x} {top} Q {} {y} {x} {} Q {} {} {} {y} Q {} {top} {x} {top} Z\"/>",
+                    x + 12,
+                    top + GATE_HEIGHT,
+                    x + GATE_WIDTH * 3 / 4,
+                    top + GATE_HEIGHT,
+                    x + GATE_WIDTH,
+                    x + GATE_WIDTH * 3 / 4,
+                )
+                .expect("writing to a string is infallible");
+
+                if matches!(gate, Gate::Xor(_, _) | Gate::Xnor(_, _)) {
+                    writeln!(
+                        w,
+                        "      <path d=\"M {} {top} Q {} {y} {} {}\"/>",
+                        x - 6,
+                        x + 6,
+                        x - 6,
+                        top + GATE_HEIGHT
+                    )
+                    .expect("writing to a string is infallible");
+                }
+            }
+        }
+
+        if matches!(
+            gate,
+            Gate::Not(_) | Gate::Nand(_, _) | Gate::Nor(_, _) | Gate::Xnor(_, _)
+        ) {
+            writeln!(
+                w,
+                "      <circle cx=\"{}\" cy=\"{y}\" r=\"{}\"/>",
+                x + GATE_WIDTH + BUBBLE / 2,
+                BUBBLE / 2
+            )
+            .expect("writing to a string is infallible");
+        }
+
+        writeln!(w, "    </g>").expect("writing to a string is infallible");
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -0,0 +1,42 @@
+use super::*;
+
+fn count(svg: &str, class: &str) -> usize {
+    svg.matches(&format!("<g class=\"{class}\">")).count()
+}
+
+#[test]
+fn render_gates() {
+    let svg = Instruction::try_from("!(a ^ b) v (c + !d)")
+        .unwrap()
+        .to_svg();
+
+    assert!(svg.starts_with("<svg"));
+    assert_eq!(4, count(&svg, "input"));
+    assert_eq!(1, count(&svg, "nand"));
+    assert_eq!(1, count(&svg, "xor"));
+    assert_eq!(1, count(&svg, "not"));
+    assert_eq!(1, count(&svg, "or"));
+}
+
+#[test]
+fn render_shared_subexpressions_once() {
+    let svg = Instruction::try_from("(a ^ b) v ((a ^ b) + c)")
+        .unwrap()
+        .to_svg();
+
+    assert_eq!(3, count(&svg, "input"));
+    assert_eq!(1, count(&svg, "and"));
+}
+
+#[test]
+fn render_linkers_as_gates() {
+    let svg = Instruction::try_from("(a -> b) ^ (a <-> b)")
+        .unwrap()
+        .to_svg();
+
+    assert_eq!(2, count(&svg, "input"));
+    assert_eq!(1, count(&svg, "not"));
+    assert_eq!(1, count(&svg, "or"));
+    assert_eq!(1, count(&svg, "xnor"));
+    assert_eq!(1, count(&svg, "and"));
+}
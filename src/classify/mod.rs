@@ -0,0 +1,224 @@
+use super::*;
+use cnf::{Cnf, Lit};
+use sat::Solver;
+use std::fmt::{self, Write as _};
+
+#[cfg(test)]
+mod tests;
+
+/// Proof that an instruction is a tautology: a refutation of its negation.
+///
+/// The negation is encoded with [`Cnf::tseitin`], and every lemma is a clause learnt by the solver
+/// that follows from the clauses and the previous lemmas by reverse unit propagation. The empty
+/// clause follows from the last one.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Certificate {
+    cnf: Cnf,
+    lemmas: Vec<Vec<Lit>>,
+}
+
+impl Certificate {
+    /// Check every step of the refutation, independently of the solver that produced it.
+    pub fn verify(&self) -> bool {
+        let mut clauses = self.cnf.clauses.clone();
+
+        for lemma in self.lemmas.iter().chain(iter::once(&vec![])) {
+            if !rup(&clauses, lemma, self.cnf.variables) {
+                return false;
+            }
+
+            clauses.push(lemma.clone());
+        }
+
+        true
+    }
+
+    /// Number of lemmas of the refutation.
+    pub fn len(&self) -> usize {
+        self.lemmas.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lemmas.is_empty()
+    }
+
+    /// The clauses of the negated instruction in DIMACS format, with the variables of the
+    /// arguments named in comments.
+    pub fn dimacs(&self) -> String {
+        let mut dimacs = String::new();
+
+        for (v, argument) in self.cnf.arguments.iter().enumerate() {
+            writeln!(dimacs, "c {} {argument}", v + 1).expect("writing to a string is infallible");
+        }
+
+        writeln!(
+            dimacs,
+            "p cnf {} {}",
+            self.cnf.variables,
+            self.cnf.clauses.len()
+        )
+        .expect("writing to a string is infallible");
+
+        for clause in &self.cnf.clauses {
+            writeln!(dimacs, "{}", dimacs_clause(clause))
+                .expect("writing to a string is infallible");
+        }
+
+        dimacs
+    }
+
+    /// The lemmas of the refutation in DRUP format, ending with the empty clause.
+    pub fn drup(&self) -> String {
+        self.lemmas
+            .iter()
+            .map(|lemma| dimacs_clause(lemma))
+            .chain(iter::once("0".to_string()))
+            .map(|line| line + "\n")
+            .collect()
+    }
+}
+
+fn dimacs_clause(clause: &[Lit]) -> String {
+    clause
+        .iter()
+        .map(|l| {
+            let v = l.variable() as i64 + 1;
+            if l.is_positive() {
+                v
+            } else {
+                -v
+            }
+        })
+        .map(|l| l.to_string())
+        .chain(iter::once("0".to_string()))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Whether asserting the negation of the clause and propagating units over the clauses leads to
+/// a conflict.
+fn rup(clauses: &[Vec<Lit>], clause: &[Lit], variables: usize) -> bool {
+    let mut values = vec![None; variables];
+    let value =
+        |values: &[Option<bool>], l: Lit| values[l.variable()].map(|v| v == l.is_positive());
+
+    for l in clause {
+        if value(&values, *l) == Some(true) {
+            return true;
+        }
+
+        values[l.variable()] = Some(!l.is_positive());
+    }
+
+    loop {
+        let mut propagated = false;
+
+        for clause in clauses {
+            let mut unassigned = None;
+            let mut open = 0;
+
+            for l in clause {
+                match value(&values, *l) {
+                    Some(true) => {
+                        open = usize::MAX;
+                        break;
+                    }
+                    Some(false) => (),
+                    None => {
+                        unassigned = Some(*l);
+                        open += 1;
+                    }
+                }
+            }
+
+            match (open, unassigned) {
+                (0, _) => return true,
+                (1, Some(l)) => {
+                    values[l.variable()] = Some(l.is_positive());
+                    propagated = true;
+                }
+                _ => (),
+            }
+        }
+
+        if !propagated {
+            return false;
+        }
+    }
+}
+
+/// Semantic class of an instruction, with the evidence that supports it.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Classification {
+    /// True under every assignment.
+    Tautology(Certificate),
+    /// False under every assignment, such as the falsifying one.
+    Contradiction(Model),
+    /// True under some assignments and false under others.
+    Contingent {
+        satisfying: Model,
+        falsifying: Model,
+    },
+}
+
+impl Classification {
+    pub fn run(instruction: &Instruction) -> Self {
+        let negation = Instruction::Not(Box::new(instruction.clone()));
+        let cnf = Cnf::tseitin(&negation);
+
+        let mut solver = Solver::with_cnf(&cnf);
+        solver.log_proof();
+
+        if !solver.solve() {
+            let lemmas = solver.proof().to_vec();
+            return Self::Tautology(Certificate { cnf, lemmas });
+        }
+
+        let falsifying = sat::model(&solver, &cnf);
+        match instruction.satisfy() {
+            Some(satisfying) => Self::Contingent {
+                satisfying,
+                falsifying,
+            },
+            None => Self::Contradiction(falsifying),
+        }
+    }
+
+    pub const fn is_tautology(&self) -> bool {
+        matches!(self, Self::Tautology(_))
+    }
+
+    pub const fn is_contradiction(&self) -> bool {
+        matches!(self, Self::Contradiction(_))
+    }
+
+    pub const fn is_contingent(&self) -> bool {
+        matches!(self, Self::Contingent { .. })
+    }
+}
+
+impl fmt::Display for Classification {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Tautology(certificate) if certificate.is_empty() => write!(f, "tautology"),
+            Self::Tautology(certificate) => write!(
+                f,
+                "tautology (certified by a refutation of {} lemmas)",
+                certificate.len()
+            ),
+            Self::Contradiction(falsifying) if falsifying.iter().next().is_none() => {
+                write!(f, "contradiction")
+            }
+            Self::Contradiction(falsifying) => {
+                write!(f, "contradiction (falsified by {falsifying})")
+            }
+            Self::Contingent {
+                satisfying,
+                falsifying,
+            } => write!(
+                f,
+                "contingent (satisfied by {satisfying}; falsified by {falsifying})"
+            ),
+        }
+    }
+}
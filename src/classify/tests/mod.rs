@@ -0,0 +1,96 @@
+use super::*;
+
+#[test]
+fn classify_tautology() {
+    let instruction = Instruction::try_from("((a -> b) ^ (b -> c)) -> (a -> c)").unwrap();
+
+    match instruction.classify() {
+        Classification::Tautology(certificate) => {
+            assert!(certificate.verify());
+            assert!(certificate.drup().ends_with("0\n"));
+            assert!(certificate
+                .dimacs()
+                .starts_with("c 1 a\nc 2 b\nc 3 c\np cnf "));
+        }
+        c => panic!("expected a tautology, found {c}"),
+    }
+}
+
+#[test]
+fn classify_hard_tautology() {
+    // the negation is a pigeonhole formula, which needs learnt clauses to be refuted
+    let (pigeons, holes) = (5, 4);
+
+    let placed = (0..pigeons).map(|p| {
+        let clause = (0..holes)
+            .map(|h| format!("p{p}h{h}"))
+            .collect::<Vec<_>>()
+            .join(" v ");
+
+        format!("({clause})")
+    });
+
+    let exclusive = (0..holes).flat_map(|h| {
+        (0..pigeons)
+            .flat_map(move |p| (p + 1..pigeons).map(move |q| format!("!(p{p}h{h} ^ p{q}h{h})")))
+    });
+
+    let expr = placed.chain(exclusive).collect::<Vec<_>>().join(" ^ ");
+    let instruction = Instruction::try_from(format!("!({expr})").as_str()).unwrap();
+
+    match instruction.classify() {
+        Classification::Tautology(certificate) => {
+            assert!(!certificate.is_empty());
+            assert!(certificate.verify());
+        }
+        c => panic!("expected a tautology, found {c}"),
+    }
+}
+
+#[test]
+fn tampered_certificate() {
+    let instruction = Instruction::try_from("a v !a").unwrap();
+
+    let Classification::Tautology(mut certificate) = instruction.classify() else {
+        panic!("expected a tautology");
+    };
+
+    certificate.cnf.clauses.clear();
+    assert!(!certificate.verify());
+}
+
+#[test]
+fn classify_contradiction() {
+    let instruction = Instruction::try_from("(a -> b) ^ a ^ !b").unwrap();
+    let classification = instruction.classify();
+
+    assert!(classification.is_contradiction());
+
+    let Classification::Contradiction(falsifying) = classification else {
+        unreachable!()
+    };
+    assert_eq!(Ok(false), instruction.evaluate_with(&falsifying));
+}
+
+#[test]
+fn classify_contingent() {
+    let instruction = Instruction::try_from("a -> (b ^ c)").unwrap();
+
+    let Classification::Contingent {
+        satisfying,
+        falsifying,
+    } = instruction.classify()
+    else {
+        panic!("expected a contingent instruction");
+    };
+
+    assert_eq!(Ok(true), instruction.evaluate_with(&satisfying));
+    assert_eq!(Ok(false), instruction.evaluate_with(&falsifying));
+}
+
+#[test]
+fn classify_constants() {
+    assert!(Instruction::True.classify().is_tautology());
+    assert!(Instruction::False.classify().is_contradiction());
+    assert_eq!("contradiction", Instruction::False.classify().to_string());
+}
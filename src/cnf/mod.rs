@@ -0,0 +1,194 @@
+use super::*;
+use std::{collections::HashMap, ops::Not};
+use traverser::Arguments;
+
+#[cfg(test)]
+mod tests;
+
+/// A variable or its negation, packed as `2 * variable + negated`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Lit(u32);
+
+impl Lit {
+    pub fn new(variable: usize, positive: bool) -> Self {
+        let variable = u32::try_from(variable).expect("the number of variables fits a literal");
+        Self(variable << 1 | u32::from(!positive))
+    }
+
+    pub const fn variable(self) -> usize {
+        (self.0 >> 1) as usize
+    }
+
+    pub const fn is_positive(self) -> bool {
+        self.0 & 1 == 0
+    }
+
+    /// Dense index of the literal, distinct for both polarities of every variable.
+    pub const fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+impl Not for Lit {
+    type Output = Self;
+
+    fn not(self) -> Self {
+        Self(self.0 ^ 1)
+    }
+}
+
+/// A formula in conjunctive normal form.
+///
+/// The first variables are the arguments of the encoded instructions, in the order of
+/// [`Cnf::arguments`]; the remaining ones are auxiliary.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Cnf {
+    pub arguments: Vec<String>,
+    pub variables: usize,
+    pub clauses: Vec<Vec<Lit>>,
+}
+
+impl Cnf {
+    /// Encode the instruction so the CNF is satisfiable if and only if the instruction is.
+    pub fn tseitin(instruction: &Instruction) -> Self {
+        let mut tseitin = Tseitin::new(Arguments::sorted(instruction));
+        let root = tseitin.encode(instruction);
+        tseitin.assert(root);
+        tseitin.finish()
+    }
+
+    pub fn variable(&mut self) -> usize {
+        self.variables += 1;
+        self.variables - 1
+    }
+}
+
+/// Tseitin encoder of instructions into a shared [`Cnf`].
+///
+/// Every compound node is defined by a fresh variable equivalent to it, so the size of the CNF is
+/// linear in the size of the instructions. Identical subexpressions are encoded once, and negations
+/// do not introduce variables.
+pub struct Tseitin<'a> {
+    cnf: Cnf,
+    arguments: HashMap<&'a str, Lit>,
+    nodes: HashMap<&'a Instruction, Lit>,
+    constant: Option<Lit>,
+}
+
+impl<'a> Tseitin<'a> {
+    /// Start an encoding whose first variables are the provided arguments.
+    pub fn new(arguments: Vec<&'a str>) -> Self {
+        let mut tseitin = Self {
+            cnf: Cnf::default(),
+            arguments: HashMap::new(),
+            nodes: HashMap::new(),
+            constant: None,
+        };
+
+        for argument in arguments {
+            tseitin.argument(argument);
+        }
+
+        tseitin
+    }
+
+    fn argument(&mut self, argument: &'a str) -> Lit {
+        if let Some(lit) = self.arguments.get(argument) {
+            return *lit;
+        }
+
+        let lit = Lit::new(self.cnf.variable(), true);
+        self.cnf.arguments.push(argument.to_string());
+        self.arguments.insert(argument, lit);
+
+        lit
+    }
+
+    /// Literal that is always true, defined by a unit clause.
+    fn constant(&mut self) -> Lit {
+        match self.constant {
+            Some(lit) => lit,
+            None => {
+                let lit = Lit::new(self.cnf.variable(), true);
+                self.cnf.clauses.push(vec![lit]);
+                *self.constant.insert(lit)
+            }
+        }
+    }
+
+    /// Add the clause to the encoding.
+    pub fn clause(&mut self, clause: Vec<Lit>) {
+        self.cnf.clauses.push(clause);
+    }
+
+    /// Require the literal to be true.
+    pub fn assert(&mut self, lit: Lit) {
+        self.clause(vec![lit]);
+    }
+
+    /// Allocate an auxiliary variable that is not bound to any node.
+    pub fn fresh(&mut self) -> Lit {
+        Lit::new(self.cnf.variable(), true)
+    }
+
+    /// Literal equivalent to the instruction.
+    ///
+    /// Arguments that were not provided upfront are appended to the arguments of the CNF.
+    pub fn encode(&mut self, instruction: &'a Instruction) -> Lit {
+        use Instruction::*;
+        let (l, r) = match instruction {
+            True => return self.constant(),
+            False => return !self.constant(),
+            Argument(a) => return self.argument(a),
+            Not(x) => return !self.encode(x),
+
+            And(l, r)
+            | Or(l, r)
+            | Xor(l, r)
+            | Conditional(l, r)
+            | Biconditional(l, r)
+            | Equals(l, r) => (l, r),
+        };
+
+        if let Some(lit) = self.nodes.get(instruction) {
+            return *lit;
+        }
+
+        let l = self.encode(l);
+        let r = self.encode(r);
+        let g = self.fresh();
+
+        self.cnf.clauses.extend(definition(instruction, g, l, r));
+        self.nodes.insert(instruction, g);
+
+        g
+    }
+
+    pub fn finish(self) -> Cnf {
+        self.cnf
+    }
+}
+
+/// Clauses that make `g` equivalent to the binary operator of the instruction applied to `l` and
+/// `r`.
+pub fn definition(instruction: &Instruction, g: Lit, l: Lit, r: Lit) -> Vec<Vec<Lit>> {
+    use Instruction::*;
+
+    match instruction {
+        And(_, _) => vec![vec![!g, l], vec![!g, r], vec![g, !l, !r]],
+        Or(_, _) => vec![vec![g, !l], vec![g, !r], vec![!g, l, r]],
+        Conditional(_, _) => vec![vec![g, l], vec![g, !r], vec![!g, !l, r]],
+        Xor(_, _) => vec![
+            vec![!g, l, r],
+            vec![!g, !l, !r],
+            vec![g, !l, r],
+            vec![g, l, !r],
+        ],
+        _ => vec![
+            vec![g, l, r],
+            vec![g, !l, !r],
+            vec![!g, !l, r],
+            vec![!g, l, !r],
+        ],
+    }
+}
@@ -0,0 +1,43 @@
+use super::*;
+use sat::Solver;
+
+#[test]
+fn literals() {
+    let lit = Lit::new(3, true);
+
+    assert_eq!(3, lit.variable());
+    assert!(lit.is_positive());
+    assert!(!(!lit).is_positive());
+    assert_eq!(lit, !!lit);
+    assert_ne!(lit.index(), (!lit).index());
+}
+
+#[test]
+fn tseitin_is_equisatisfiable_per_row() {
+    let instruction = Instruction::try_from("(a -> !b) + (c <-> a) v !(b = c) ^ c").unwrap();
+    let cnf = Cnf::tseitin(&instruction);
+
+    assert_eq!(vec!["a", "b", "c"], cnf.arguments);
+
+    for row in instruction.evaluate().unwrap().rows {
+        let (eval, inputs) = row.split_last().unwrap();
+        let mut solver = Solver::with_cnf(&cnf);
+
+        for (v, value) in inputs.iter().enumerate() {
+            solver.add_clause(&[Lit::new(v, *value)]);
+        }
+
+        assert_eq!(*eval, solver.solve());
+    }
+}
+
+#[test]
+fn tseitin_shares_subexpressions() {
+    let shared = Instruction::try_from("(a ^ b) v !(a ^ b)").unwrap();
+    let single = Instruction::try_from("a ^ b").unwrap();
+
+    assert_eq!(
+        Cnf::tseitin(&single).variables + 1,
+        Cnf::tseitin(&shared).variables
+    );
+}
@@ -0,0 +1,333 @@
+use super::*;
+use std::collections::{HashMap, HashSet};
+use traverser::Arguments;
+
+#[cfg(test)]
+mod tests;
+
+const RUST_KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern",
+    "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub",
+    "ref", "return", "self", "Self", "static", "struct", "super", "trait", "true", "type",
+    "unsafe", "use", "where", "while", "abstract", "become", "box", "do", "final", "macro",
+    "override", "priv", "typeof", "unsized", "virtual", "yield", "try",
+];
+
+const C_KEYWORDS: &[&str] = &[
+    "auto", "bool", "break", "case", "char", "const", "continue", "default", "do", "double",
+    "else", "enum", "extern", "false", "float", "for", "goto", "if", "inline", "int", "long",
+    "register", "restrict", "return", "short", "signed", "sizeof", "static", "struct", "switch",
+    "true", "typedef", "union", "unsigned", "void", "volatile", "while",
+];
+
+const PYTHON_KEYWORDS: &[&str] = &[
+    "False", "None", "True", "and", "as", "assert", "async", "await", "bool", "break", "class",
+    "continue", "def", "del", "elif", "else", "except", "finally", "for", "from", "global", "if",
+    "import", "in", "is", "lambda", "nonlocal", "not", "or", "pass", "raise", "return", "try",
+    "while", "with", "yield",
+];
+
+const JAVASCRIPT_KEYWORDS: &[&str] = &[
+    "arguments",
+    "await",
+    "break",
+    "case",
+    "catch",
+    "class",
+    "const",
+    "continue",
+    "debugger",
+    "default",
+    "delete",
+    "do",
+    "else",
+    "enum",
+    "eval",
+    "export",
+    "extends",
+    "false",
+    "finally",
+    "for",
+    "function",
+    "if",
+    "implements",
+    "import",
+    "in",
+    "instanceof",
+    "interface",
+    "let",
+    "new",
+    "null",
+    "package",
+    "private",
+    "protected",
+    "public",
+    "return",
+    "static",
+    "super",
+    "switch",
+    "this",
+    "throw",
+    "true",
+    "try",
+    "typeof",
+    "var",
+    "void",
+    "while",
+    "with",
+    "yield",
+];
+
+/// Target language of the code generator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Language {
+    Rust,
+    C,
+    Python,
+    JavaScript,
+    /// A `WHERE` predicate over boolean columns.
+    Sql,
+}
+
+impl TryFrom<&str> for Language {
+    type Error = String;
+
+    fn try_from(language: &str) -> Result<Self, Self::Error> {
+        match language.to_lowercase().as_str() {
+            "rust" | "rs" => Ok(Self::Rust),
+            "c" => Ok(Self::C),
+            "python" | "py" => Ok(Self::Python),
+            "javascript" | "js" => Ok(Self::JavaScript),
+            "sql" => Ok(Self::Sql),
+            _ => Err(format!("unsupported language `{language}`")),
+        }
+    }
+}
+
+impl Language {
+    const fn keywords(&self) -> &'static [&'static str] {
+        match self {
+            Self::Rust => RUST_KEYWORDS,
+            Self::C => C_KEYWORDS,
+            Self::Python => PYTHON_KEYWORDS,
+            Self::JavaScript => JAVASCRIPT_KEYWORDS,
+            Self::Sql => &[],
+        }
+    }
+
+    /// Convert an arbitrary name into an identifier that is valid for the language.
+    fn identifier(&self, name: &str) -> String {
+        if let Self::Sql = self {
+            return format!("\"{}\"", name.replace('"', "\"\""));
+        }
+
+        // characters outside ASCII are escaped by their code point, so they stay distinct
+        let mut identifier = name
+            .chars()
+            .map(|c| match c {
+                c if c.is_ascii_alphanumeric() => c.to_string(),
+                c if c.is_ascii() => "_".to_string(),
+                c => format!("_u{:x}", c as u32),
+            })
+            .collect::<String>();
+
+        // a bare `_` is a placeholder rather than a variable in most of the languages
+        if identifier.chars().all(|c| c == '_') {
+            identifier.insert(0, 'v');
+        } else if identifier.starts_with(|c: char| c.is_ascii_digit()) {
+            identifier.insert(0, '_');
+        }
+
+        if self.keywords().contains(&identifier.as_str()) {
+            identifier.push('_');
+        }
+
+        identifier
+    }
+}
+
+/// Code generator that compiles an instruction into a function of the target language.
+///
+/// The parameters of the function are the distinct arguments of the instruction, in the same
+/// alphabetical order of the columns of the evaluation table.
+pub struct Codegen<'a> {
+    language: Language,
+    parameters: Vec<&'a str>,
+    identifiers: HashMap<&'a str, String>,
+}
+
+impl<'a> Codegen<'a> {
+    pub fn compile(instruction: &'a Instruction, language: Language, name: &str) -> String {
+        let parameters = Arguments::sorted(instruction);
+
+        // distinct names might collide once converted to identifiers
+        let mut taken = HashSet::new();
+        let identifiers = parameters
+            .iter()
+            .map(|p| {
+                let base = language.identifier(p);
+                let mut identifier = base.clone();
+                let mut suffix = 1;
+                while !taken.insert(identifier.clone()) {
+                    suffix += 1;
+                    identifier = format!("{base}_{suffix}");
+                }
+                (*p, identifier)
+            })
+            .collect();
+
+        let codegen = Self {
+            language,
+            parameters,
+            identifiers,
+        };
+
+        codegen.function(instruction, &language.identifier(name))
+    }
+
+    fn function(&self, instruction: &Instruction, name: &str) -> String {
+        let body = self.expression(instruction);
+
+        // the outermost parenthesis are redundant in a return expression
+        let body = match instruction {
+            Instruction::True | Instruction::False | Instruction::Argument(_) => body,
+            Instruction::Not(_)
+                if matches!(
+                    self.language,
+                    Language::Rust | Language::C | Language::JavaScript
+                ) =>
+            {
+                body
+            }
+            _ => body[1..body.len() - 1].to_string(),
+        };
+        let parameters = self.parameters.iter().map(|p| self.identifiers[p].as_str());
+
+        match self.language {
+            Language::Rust => {
+                let parameters = parameters
+                    .map(|p| format!("{p}: bool"))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+
+                format!("pub fn {name}({parameters}) -> bool {{\n    {body}\n}}\n")
+            }
+
+            Language::C => {
+                let parameters = parameters.map(|p| format!("bool {p}")).collect::<Vec<_>>();
+
+                let parameters = if parameters.is_empty() {
+                    "void".to_string()
+                } else {
+                    parameters.join(", ")
+                };
+
+                format!(
+                    "#include <stdbool.h>\n\nbool {name}({parameters}) {{\n    return {body};\n}}\n"
+                )
+            }
+
+            Language::Python => {
+                let parameters = parameters
+                    .map(|p| format!("{p}: bool"))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+
+                format!("def {name}({parameters}) -> bool:\n    return {body}\n")
+            }
+
+            Language::JavaScript => {
+                let parameters = parameters.collect::<Vec<_>>().join(", ");
+
+                format!("function {name}({parameters}) {{\n    return {body};\n}}\n")
+            }
+
+            Language::Sql => format!("WHERE {body}\n"),
+        }
+    }
+
+    fn expression(&self, instruction: &Instruction) -> String {
+        use Instruction::*;
+        use Language::*;
+
+        match instruction {
+            True => match self.language {
+                Rust | C | JavaScript => "true".into(),
+                Python => "True".into(),
+                Sql => "TRUE".into(),
+            },
+
+            False => match self.language {
+                Rust | C | JavaScript => "false".into(),
+                Python => "False".into(),
+                Sql => "FALSE".into(),
+            },
+
+            Argument(a) => self.identifiers[a.as_str()].clone(),
+
+            Not(x) => self.not(self.expression(x)),
+
+            And(l, r) => {
+                let op = match self.language {
+                    Rust | C | JavaScript => "&&",
+                    Python => "and",
+                    Sql => "AND",
+                };
+                self.binary(op, l, r)
+            }
+
+            Or(l, r) => {
+                let op = match self.language {
+                    Rust | C | JavaScript => "||",
+                    Python => "or",
+                    Sql => "OR",
+                };
+                self.binary(op, l, r)
+            }
+
+            Xor(l, r) => {
+                let op = match self.language {
+                    Rust | C | Python => "!=",
+                    JavaScript => "!==",
+                    Sql => "<>",
+                };
+                self.binary(op, l, r)
+            }
+
+            Conditional(l, r) => {
+                let op = match self.language {
+                    Rust | C | JavaScript => "||",
+                    Python => "or",
+                    Sql => "OR",
+                };
+                let l = self.not(self.expression(l));
+                let r = self.expression(r);
+                format!("({l} {op} {r})")
+            }
+
+            Biconditional(l, r) | Equals(l, r) => {
+                let op = match self.language {
+                    Rust | C | Python => "==",
+                    JavaScript => "===",
+                    Sql => "=",
+                };
+                self.binary(op, l, r)
+            }
+        }
+    }
+
+    fn not(&self, x: String) -> String {
+        match self.language {
+            Language::Rust | Language::C | Language::JavaScript => format!("!{x}"),
+            Language::Python => format!("(not {x})"),
+            Language::Sql => format!("(NOT {x})"),
+        }
+    }
+
+    fn binary(&self, op: &str, l: &Instruction, r: &Instruction) -> String {
+        let l = self.expression(l);
+        let r = self.expression(r);
+
+        format!("({l} {op} {r})")
+    }
+}
@@ -0,0 +1,69 @@
+use super::*;
+
+fn compile(expr: &str, language: Language) -> String {
+    Instruction::try_from(expr).unwrap().compile(language, "f")
+}
+
+#[test]
+fn compile_rust() {
+    assert_eq!(
+        "pub fn f(a: bool, b: bool) -> bool {\n    (!a || b) == (b != a)\n}\n",
+        compile("(a -> b) = (b + a)", Language::Rust)
+    );
+}
+
+#[test]
+fn compile_c() {
+    assert_eq!(
+        "#include <stdbool.h>\n\nbool f(bool a, bool b) {\n    return !a && b;\n}\n",
+        compile("!a ^ b", Language::C)
+    );
+}
+
+#[test]
+fn compile_python() {
+    assert_eq!(
+        "def f(a: bool, b: bool) -> bool:\n    return not (a or b)\n",
+        compile("!(a v b)", Language::Python)
+    );
+}
+
+#[test]
+fn compile_javascript() {
+    assert_eq!(
+        "function f(a, b) {\n    return a !== b;\n}\n",
+        compile("a + b", Language::JavaScript)
+    );
+}
+
+#[test]
+fn compile_sql() {
+    assert_eq!(
+        "WHERE \"flag one\" AND (NOT \"b\")\n",
+        compile("'flag one' ^ !b", Language::Sql)
+    );
+}
+
+#[test]
+fn compile_sanitizes_identifiers() {
+    assert_eq!(
+        "pub fn f(_1st: bool, a_b: bool, a_b_2: bool, fn_: bool) -> bool {\n    (a_b && a_b_2) && (_1st || fn_)\n}\n",
+        compile("(a-b ^ a_b) ^ ('1st' v fn)", Language::Rust)
+    );
+}
+
+#[test]
+fn compile_symbolic_identifiers() {
+    assert_eq!(
+        "pub fn f(v_: bool, a: bool) -> bool {\n    v_ && a\n}\n",
+        compile("'-' ^ a", Language::Rust)
+    );
+    assert_eq!(
+        "def f(_ue9: bool, _ufc: bool) -> bool:\n    return _ue9 or _ufc\n",
+        compile("'é' v 'ü'", Language::Python)
+    );
+    assert_eq!(
+        "#include <stdbool.h>\n\nbool f(bool v_, bool v__2) {\n    return v__2 != v_;\n}\n",
+        compile("'-' + '+'", Language::C)
+    );
+}
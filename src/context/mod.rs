@@ -1,26 +1,141 @@
 use super::*;
+use bitwise::BitEvaluator;
+use cnf::Lit;
+use sat::Solver;
 use std::collections::HashMap;
+use traverser::Arguments;
 
+#[cfg(test)]
+mod tests;
+
+/// Maximum number of arguments of the instructions compared by truth table. Larger instructions
+/// are compared by the SAT solver, since their tables grow exponentially.
+const TABLE_ARGUMENTS: usize = 16;
+
+/// Equivalence oracle of the optimizer.
+///
+/// Two instructions are equivalent if they agree under every assignment of the union of their
+/// arguments. That is decided by their truth tables over the union, up to [`TABLE_ARGUMENTS`],
+/// and by the SAT solver beyond it. Every answer is cached.
 #[derive(Debug, Default)]
 pub struct Context {
-    cache: HashMap<Instruction, Table>,
+    equivalences: HashMap<(Instruction, Instruction), bool>,
+    encoding: Encoding,
 }
 
 impl Context {
     pub fn check_equivalence(&mut self, a: &Instruction, b: &Instruction) -> bool {
-        if !self.cache.contains_key(a) {
-            let ev = a.evaluate().expect("internal context should be infallible");
-            self.cache.insert(a.clone(), ev);
+        if a == b {
+            return true;
         }
 
-        if !self.cache.contains_key(b) {
-            let ev = b.evaluate().expect("internal context should be infallible");
-            self.cache.insert(b.clone(), ev);
+        let key = match a < b {
+            true => (a.clone(), b.clone()),
+            false => (b.clone(), a.clone()),
+        };
+
+        if let Some(equivalent) = self.equivalences.get(&key) {
+            return *equivalent;
         }
 
-        let a = self.cache.get(a).expect("key was checked");
-        let b = self.cache.get(b).expect("key was checked");
+        let mut arguments = Arguments::sorted(a);
+        arguments.extend(Arguments::sorted(b));
+        arguments.sort();
+        arguments.dedup();
+
+        let equivalent = if arguments.len() <= TABLE_ARGUMENTS {
+            let ta = BitEvaluator::truth(a, arguments.clone());
+            let tb = BitEvaluator::truth(b, arguments);
+
+            matches!((ta, tb), (Ok(ta), Ok(tb)) if ta == tb)
+        } else {
+            self.encoding.is_equivalent(a, b)
+        };
+
+        self.equivalences.insert(key, equivalent);
+        equivalent
+    }
+}
+
+/// Incremental Tseitin encoding of every instruction compared by the SAT solver, so a node shared
+/// by several comparisons is encoded once and the learnt clauses are kept between them.
+#[derive(Debug, Default)]
+struct Encoding {
+    solver: Solver,
+    variables: usize,
+    arguments: HashMap<String, Lit>,
+    nodes: HashMap<Instruction, Lit>,
+    constant: Option<Lit>,
+}
+
+impl Encoding {
+    fn fresh(&mut self) -> Lit {
+        self.variables += 1;
+        self.solver.reserve(self.variables);
+
+        Lit::new(self.variables - 1, true)
+    }
+
+    fn encode(&mut self, instruction: &Instruction) -> Lit {
+        use Instruction::*;
+
+        let (l, r) = match instruction {
+            True | False => {
+                let constant = match self.constant {
+                    Some(lit) => lit,
+                    None => {
+                        let lit = self.fresh();
+                        self.solver.add_clause(&[lit]);
+                        *self.constant.insert(lit)
+                    }
+                };
+
+                return if *instruction == True {
+                    constant
+                } else {
+                    !constant
+                };
+            }
+            Argument(a) => {
+                if let Some(lit) = self.arguments.get(a) {
+                    return *lit;
+                }
+
+                let lit = self.fresh();
+                self.arguments.insert(a.clone(), lit);
+                return lit;
+            }
+            Not(x) => return !self.encode(x),
+
+            And(l, r)
+            | Or(l, r)
+            | Xor(l, r)
+            | Conditional(l, r)
+            | Biconditional(l, r)
+            | Equals(l, r) => (l, r),
+        };
+
+        if let Some(lit) = self.nodes.get(instruction) {
+            return *lit;
+        }
+
+        let l = self.encode(l);
+        let r = self.encode(r);
+        let g = self.fresh();
+
+        for clause in cnf::definition(instruction, g, l, r) {
+            self.solver.add_clause(&clause);
+        }
+
+        self.nodes.insert(instruction.clone(), g);
+        g
+    }
+
+    /// Whether no assignment satisfies one instruction and falsifies the other.
+    fn is_equivalent(&mut self, a: &Instruction, b: &Instruction) -> bool {
+        let a = self.encode(a);
+        let b = self.encode(b);
 
-        a == b
+        !self.solver.solve_with(&[a, !b]) && !self.solver.solve_with(&[!a, b])
     }
 }
@@ -0,0 +1,59 @@
+use super::*;
+
+fn chain(n: usize) -> String {
+    (0..n)
+        .map(|i| format!("(x{i} -> x{})", i + 1))
+        .collect::<Vec<_>>()
+        .join(" ^ ")
+}
+
+#[test]
+fn equivalence_over_different_arguments() {
+    let cases = [
+        ("a", "a ^ (b v !b)", true),
+        ("a v (b ^ !b)", "a", true),
+        ("a -> b", "!a v b v (c ^ !c)", true),
+        ("a", "a ^ b", false),
+    ];
+
+    // the same instructions, padded with a tautology over enough arguments to leave truth tables
+    let padding = format!(
+        "({} v !({}))",
+        chain(TABLE_ARGUMENTS),
+        chain(TABLE_ARGUMENTS)
+    );
+
+    let mut context = Context::default();
+    for (a, b, equivalent) in cases {
+        let a = Instruction::try_from(a).unwrap();
+        let b = Instruction::try_from(b).unwrap();
+        assert_eq!(equivalent, context.check_equivalence(&a, &b), "{a} {b}");
+        assert_eq!(equivalent, context.check_equivalence(&b, &a), "{b} {a}");
+
+        let padded = format!("({b}) ^ {padding}");
+        let padded = Instruction::try_from(padded.as_str()).unwrap();
+        assert!(Arguments::sorted(&padded).len() > TABLE_ARGUMENTS);
+        assert_eq!(
+            equivalent,
+            context.check_equivalence(&a, &padded),
+            "{a} {padded}"
+        );
+    }
+}
+
+#[test]
+fn encoding_is_shared() {
+    let mut context = Context::default();
+
+    let a = Instruction::try_from(chain(40).as_str()).unwrap();
+    let b = Instruction::try_from(format!("{} ^ (y v !y)", chain(40)).as_str()).unwrap();
+    let c = Instruction::try_from(format!("{} ^ y", chain(40)).as_str()).unwrap();
+
+    assert!(context.check_equivalence(&a, &b));
+    let variables = context.encoding.variables;
+
+    assert!(!context.check_equivalence(&a, &c));
+    // the chain and `y` were encoded by the first comparison, so only the conjunction is new
+    assert_eq!(variables + 1, context.encoding.variables);
+    assert_eq!(2, context.equivalences.len());
+}
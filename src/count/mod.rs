@@ -0,0 +1,173 @@
+use super::*;
+use cnf::{Cnf, Lit};
+use num_bigint::BigUint;
+use std::collections::{BTreeSet, HashMap};
+
+#[cfg(test)]
+mod tests;
+
+/// Residual clauses over the unassigned variables, each sorted, in sorted order.
+type Component = Vec<Vec<Lit>>;
+
+/// Exact model counter running a DPLL search with component caching on the Tseitin encoding.
+///
+/// Every auxiliary variable of the encoding is defined by the arguments, so the CNF has as many
+/// models as the instruction. After every decision and its unit propagation, the residual clauses
+/// are split into components that share no variables, which are counted independently and
+/// multiplied. The count of every component is cached, so the same subproblem reached through
+/// different decisions is counted once.
+#[derive(Debug, Default)]
+pub struct Counter {
+    cache: HashMap<Component, BigUint>,
+}
+
+impl Counter {
+    pub fn count(instruction: &Instruction) -> BigUint {
+        Self::default().count_cnf(&Cnf::tseitin(instruction))
+    }
+
+    fn count_cnf(&mut self, cnf: &Cnf) -> BigUint {
+        let Some((clauses, assigned)) = propagate(&cnf.clauses, &[]) else {
+            return BigUint::default();
+        };
+
+        let free = cnf.variables - assigned - variables(&clauses).len();
+
+        self.count_clauses(clauses) << free
+    }
+
+    /// Count the models of the clauses over the variables that occur in them.
+    fn count_clauses(&mut self, clauses: Vec<Vec<Lit>>) -> BigUint {
+        components(clauses)
+            .into_iter()
+            .map(|component| self.count_component(component))
+            .product()
+    }
+
+    fn count_component(&mut self, component: Component) -> BigUint {
+        if let Some(count) = self.cache.get(&component) {
+            return count.clone();
+        }
+
+        let occurring = variables(&component);
+        let variable = branch(&component);
+
+        let mut count = BigUint::default();
+        for positive in [false, true] {
+            let lit = Lit::new(variable, positive);
+
+            if let Some((clauses, assigned)) = propagate(&component, &[lit]) {
+                let free = occurring.len() - assigned - variables(&clauses).len();
+                count += self.count_clauses(clauses) << free;
+            }
+        }
+
+        self.cache.insert(component, count.clone());
+        count
+    }
+}
+
+/// The most frequent variable of the component.
+///
+/// The variables that define the nodes of the encoding occur in the clauses of both their parent
+/// and their children, so they are decided before the arguments, cutting the component at the
+/// node.
+fn branch(component: &Component) -> usize {
+    let mut occurrences = HashMap::<usize, usize>::new();
+    for lit in component.iter().flatten() {
+        *occurrences.entry(lit.variable()).or_default() += 1;
+    }
+
+    occurrences
+        .into_iter()
+        .max_by_key(|(v, n)| (*n, std::cmp::Reverse(*v)))
+        .map(|(v, _)| v)
+        .expect("components are not empty")
+}
+
+fn variables(clauses: &[Vec<Lit>]) -> BTreeSet<usize> {
+    clauses.iter().flatten().map(|l| l.variable()).collect()
+}
+
+/// Assign the literals and propagate the unit clauses, returning the residual clauses and the
+/// number of assigned variables, or `None` on a conflict.
+fn propagate(clauses: &[Vec<Lit>], lits: &[Lit]) -> Option<(Vec<Vec<Lit>>, usize)> {
+    let mut values = HashMap::new();
+    let mut units = lits.to_vec();
+    let mut clauses = clauses.to_vec();
+
+    loop {
+        for unit in units.drain(..) {
+            match values.insert(unit.variable(), unit.is_positive()) {
+                Some(v) if v != unit.is_positive() => return None,
+                _ => (),
+            }
+        }
+
+        let value = |l: &Lit| values.get(&l.variable()).map(|v| *v == l.is_positive());
+        let mut residual = Vec::with_capacity(clauses.len());
+
+        for clause in clauses {
+            if clause.iter().any(|l| value(l) == Some(true)) {
+                continue;
+            }
+
+            let open = clause
+                .into_iter()
+                .filter(|l| value(l).is_none())
+                .collect::<Vec<_>>();
+
+            match open.len() {
+                0 => return None,
+                1 => units.push(open[0]),
+                _ => residual.push(open),
+            }
+        }
+
+        clauses = residual;
+
+        if units.is_empty() {
+            return Some((clauses, values.len()));
+        }
+    }
+}
+
+/// Split the clauses into groups that share no variables, each in canonical order.
+fn components(clauses: Vec<Vec<Lit>>) -> Vec<Component> {
+    let mut parents = HashMap::<usize, usize>::new();
+
+    fn root(parents: &mut HashMap<usize, usize>, v: usize) -> usize {
+        let parent = *parents.entry(v).or_insert(v);
+        if parent == v {
+            return v;
+        }
+
+        let r = root(parents, parent);
+        parents.insert(v, r);
+        r
+    }
+
+    for clause in &clauses {
+        let first = root(&mut parents, clause[0].variable());
+        for lit in &clause[1..] {
+            let r = root(&mut parents, lit.variable());
+            parents.insert(r, first);
+        }
+    }
+
+    let mut groups = HashMap::<usize, Component>::new();
+    for mut clause in clauses {
+        clause.sort_unstable();
+        let r = root(&mut parents, clause[0].variable());
+        groups.entry(r).or_default().push(clause);
+    }
+
+    groups
+        .into_values()
+        .map(|mut component| {
+            component.sort_unstable();
+            component.dedup();
+            component
+        })
+        .collect()
+}
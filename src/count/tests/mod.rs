@@ -0,0 +1,72 @@
+use super::*;
+
+fn count(expr: &str) -> BigUint {
+    Instruction::try_from(expr).unwrap().count_models()
+}
+
+#[test]
+fn count_agrees_with_truth_table() {
+    let exprs = [
+        "a",
+        "a v b",
+        "a ^ !a",
+        "a -> b",
+        "!(a + b) = !c -> (x <-> a)",
+        "(a ^ b) v (c ^ d) v (e ^ f)",
+        "(a + b + c + d) ^ (a v e)",
+    ];
+
+    for expr in exprs {
+        let instruction = Instruction::try_from(expr).unwrap();
+        let expected = instruction
+            .evaluate()
+            .unwrap()
+            .rows
+            .iter()
+            .filter(|r| r[r.len() - 1])
+            .count();
+
+        assert_eq!(
+            BigUint::from(expected),
+            instruction.count_models(),
+            "{expr}"
+        );
+    }
+}
+
+#[test]
+fn count_constants() {
+    assert_eq!(BigUint::from(1u8), Instruction::True.count_models());
+    assert_eq!(BigUint::default(), Instruction::False.count_models());
+}
+
+#[test]
+fn count_independent_components() {
+    // 100 disjoint clauses of 3 variables, each with 7 models
+    let expr = (0..100)
+        .map(|i| format!("(a{i} v b{i} v c{i})"))
+        .collect::<Vec<_>>()
+        .join(" ^ ");
+
+    assert_eq!(BigUint::from(7u8).pow(100), count(&expr));
+}
+
+#[test]
+fn count_chains() {
+    let n = 300u32;
+    let chain = (0..n - 1)
+        .map(|i| format!("(x{i} -> x{})", i + 1))
+        .collect::<Vec<_>>()
+        .join(" ^ ");
+
+    // the models are the suffixes of true variables
+    assert_eq!(BigUint::from(n + 1), count(&chain));
+
+    // every variable but the first is free once its predecessor is false
+    let parity = (0..n)
+        .map(|i| format!("x{i}"))
+        .collect::<Vec<_>>()
+        .join(" + ");
+
+    assert_eq!(BigUint::from(1u8) << (n - 1), count(&parity));
+}
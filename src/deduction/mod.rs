@@ -0,0 +1,451 @@
+use super::*;
+use std::collections::HashMap;
+use std::fmt;
+
+#[cfg(test)]
+mod tests;
+
+/// Inference rule of a natural deduction step.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Inference {
+    Premise,
+    /// Opens a subproof.
+    Assumption,
+    Reiteration,
+    AndIntro,
+    AndElim,
+    OrIntro,
+    OrElim,
+    ConditionalIntro,
+    ConditionalElim,
+    BiconditionalIntro,
+    BiconditionalElim,
+    /// `!p` from a subproof that assumes `p` and derives `⊥`.
+    NotIntro,
+    /// `⊥` from `p` and `!p`.
+    NotElim,
+    /// Any formula from `⊥`.
+    Explosion,
+    /// `p` from `!!p`.
+    DoubleNegation,
+    /// `p` from a subproof that assumes `!p` and derives `⊥`.
+    ReductioAdAbsurdum,
+}
+
+impl TryFrom<&str> for Inference {
+    type Error = String;
+
+    fn try_from(name: &str) -> Result<Self, Self::Error> {
+        let inference = match name.to_lowercase().as_str() {
+            "premise" | "pr" => Self::Premise,
+            "assumption" | "assume" | "hyp" | "as" | "a" => Self::Assumption,
+            "reiteration" | "reit" | "r" => Self::Reiteration,
+            "∧i" | "^i" | "&i" | "andi" => Self::AndIntro,
+            "∧e" | "^e" | "&e" | "ande" => Self::AndElim,
+            "∨i" | "vi" | "ori" => Self::OrIntro,
+            "∨e" | "ve" | "ore" => Self::OrElim,
+            "→i" | "->i" | "⊃i" | "condi" => Self::ConditionalIntro,
+            "→e" | "->e" | "⊃e" | "conde" | "mp" => Self::ConditionalElim,
+            "↔i" | "<->i" | "≡i" | "bicondi" => Self::BiconditionalIntro,
+            "↔e" | "<->e" | "≡e" | "biconde" => Self::BiconditionalElim,
+            "¬i" | "!i" | "~i" | "noti" => Self::NotIntro,
+            "¬e" | "!e" | "~e" | "note" | "⊥i" | "_|_i" | "boti" => Self::NotElim,
+            "⊥e" | "_|_e" | "bote" | "x" => Self::Explosion,
+            "¬¬e" | "!!e" | "~~e" | "dne" => Self::DoubleNegation,
+            "raa" | "ip" => Self::ReductioAdAbsurdum,
+            _ => return Err(format!("unknown rule `{name}`")),
+        };
+
+        Ok(inference)
+    }
+}
+
+impl fmt::Display for Inference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Premise => "premise",
+            Self::Assumption => "assumption",
+            Self::Reiteration => "R",
+            Self::AndIntro => "∧I",
+            Self::AndElim => "∧E",
+            Self::OrIntro => "∨I",
+            Self::OrElim => "∨E",
+            Self::ConditionalIntro => "→I",
+            Self::ConditionalElim => "→E",
+            Self::BiconditionalIntro => "↔I",
+            Self::BiconditionalElim => "↔E",
+            Self::NotIntro => "¬I",
+            Self::NotElim => "¬E",
+            Self::Explosion => "⊥E",
+            Self::DoubleNegation => "¬¬E",
+            Self::ReductioAdAbsurdum => "RAA",
+        };
+
+        write!(f, "{name}")
+    }
+}
+
+impl Inference {
+    /// Number of cited lines and subproofs.
+    const fn arity(&self) -> (usize, usize) {
+        match self {
+            Self::Premise | Self::Assumption => (0, 0),
+            Self::Reiteration
+            | Self::AndElim
+            | Self::OrIntro
+            | Self::Explosion
+            | Self::DoubleNegation => (1, 0),
+            Self::AndIntro | Self::ConditionalElim | Self::BiconditionalElim | Self::NotElim => {
+                (2, 0)
+            }
+            Self::OrElim => (1, 2),
+            Self::ConditionalIntro | Self::NotIntro | Self::ReductioAdAbsurdum => (0, 1),
+            Self::BiconditionalIntro => (0, 2),
+        }
+    }
+
+    /// What the rule derives, and from what.
+    const fn description(&self) -> &'static str {
+        match self {
+            Self::Premise => "it states a premise",
+            Self::Assumption => "it opens a subproof",
+            Self::Reiteration => "it repeats a line",
+            Self::AndIntro => "it derives `p ^ q` from `p` and `q`",
+            Self::AndElim => "it derives `p` or `q` from `p ^ q`",
+            Self::OrIntro => "it derives `p v q` from `p` or `q`",
+            Self::OrElim => {
+                "it derives `r` from `p v q`, a subproof from `p` to `r`, and a subproof from `q` to `r`"
+            }
+            Self::ConditionalIntro => "it derives `p -> q` from a subproof from `p` to `q`",
+            Self::ConditionalElim => "it derives `q` from `p -> q` and `p`",
+            Self::BiconditionalIntro => {
+                "it derives `p <-> q` from a subproof from `p` to `q` and one from `q` to `p`"
+            }
+            Self::BiconditionalElim => "it derives `q` from `p <-> q` and `p`, or `p` from `q`",
+            Self::NotIntro => "it derives `!p` from a subproof from `p` to `⊥`",
+            Self::NotElim => "it derives `⊥` from `p` and `!p`",
+            Self::Explosion => "it derives anything from `⊥`",
+            Self::DoubleNegation => "it derives `p` from `!!p`",
+            Self::ReductioAdAbsurdum => "it derives `p` from a subproof from `!p` to `⊥`",
+        }
+    }
+
+    /// Whether the rule derives the formula from the cited lines and the assumption and last
+    /// line of the cited subproofs, in any order.
+    fn derives(
+        &self,
+        formula: &Instruction,
+        lines: &[&Instruction],
+        subproofs: &[(&Instruction, &Instruction)],
+    ) -> bool {
+        use Instruction::*;
+
+        let is_not = |x: &Instruction, p: &Instruction| matches!(x, Not(y) if **y == *p);
+
+        match self {
+            Self::Premise | Self::Assumption => true,
+            Self::Reiteration => formula == lines[0],
+            Self::AndIntro => pairs(lines)
+                .into_iter()
+                .any(|(a, b)| matches!(formula, And(l, r) if **l == *a && **r == *b)),
+            Self::AndElim => matches!(lines[0], And(l, r) if **l == *formula || **r == *formula),
+            Self::OrIntro => matches!(formula, Or(l, r) if **l == *lines[0] || **r == *lines[0]),
+            Self::OrElim => match (lines[0], subproofs) {
+                (Or(l, r), [a, b]) => [(a, b), (b, a)]
+                    .into_iter()
+                    .any(|(a, b)| *a.0 == **l && *b.0 == **r && a.1 == formula && b.1 == formula),
+                _ => false,
+            },
+            Self::ConditionalIntro => {
+                let (p, q) = subproofs[0];
+                matches!(formula, Conditional(l, r) if **l == *p && **r == *q)
+            }
+            Self::ConditionalElim => pairs(lines)
+                .into_iter()
+                .any(|(c, a)| matches!(c, Conditional(p, q) if **p == *a && **q == *formula)),
+            Self::BiconditionalIntro => match (formula, subproofs) {
+                (Biconditional(p, q) | Equals(p, q), [a, b]) => [(a, b), (b, a)]
+                    .into_iter()
+                    .any(|(a, b)| *a.0 == **p && *a.1 == **q && *b.0 == **q && *b.1 == **p),
+                _ => false,
+            },
+            Self::BiconditionalElim => pairs(lines).into_iter().any(|(b, a)| match b {
+                Biconditional(p, q) | Equals(p, q) => {
+                    (**p == *a && **q == *formula) || (**q == *a && **p == *formula)
+                }
+                _ => false,
+            }),
+            Self::NotIntro => {
+                let (p, bottom) = subproofs[0];
+                *bottom == False && is_not(formula, p)
+            }
+            Self::NotElim => {
+                *formula == False && pairs(lines).into_iter().any(|(p, n)| is_not(n, p))
+            }
+            Self::Explosion => *lines[0] == False,
+            // the grammar reads a repeated `!!p` as `p`, so only `!(!p)` keeps both negations
+            Self::DoubleNegation => {
+                matches!(lines[0], Not(x) if is_not(x, formula)) || lines[0] == formula
+            }
+            Self::ReductioAdAbsurdum => {
+                let (n, bottom) = subproofs[0];
+                *bottom == False && is_not(n, formula)
+            }
+        }
+    }
+}
+
+/// Both orders of two cited lines.
+fn pairs<'a>(lines: &[&'a Instruction]) -> [(&'a Instruction, &'a Instruction); 2] {
+    [(lines[0], lines[1]), (lines[1], lines[0])]
+}
+
+/// Citation of a previous line, or of a closed subproof by its first and last lines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Reference {
+    Line(usize),
+    Subproof(usize, usize),
+}
+
+impl fmt::Display for Reference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Line(n) => write!(f, "{n}"),
+            Self::Subproof(a, b) => write!(f, "{a}-{b}"),
+        }
+    }
+}
+
+/// Numbered step of a proof.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Line {
+    pub number: usize,
+    /// Number of subproofs the line is nested in.
+    pub depth: usize,
+    /// The formula, where `⊥` is [`Instruction::False`].
+    pub formula: Instruction,
+    pub inference: Inference,
+    pub references: Vec<Reference>,
+}
+
+/// Fitch-style natural deduction proof.
+///
+/// Every line is written `n. | formula : rule refs`, with one `|` for each subproof the line is
+/// nested in. A subproof starts with an assumption, and an assumption at the depth of the
+/// current subproof starts a sibling one. Lines are cited by number and subproofs by range, as in
+/// `→E 1, 2` or `→I 3-5`; the contradiction is written `⊥` or `_|_`. Blank lines, `#` comments and
+/// separator lines such as `| ---` are ignored.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Proof {
+    lines: Vec<Line>,
+}
+
+impl TryFrom<&str> for Proof {
+    type Error = String;
+
+    fn try_from(text: &str) -> Result<Self, Self::Error> {
+        let mut lines = vec![];
+
+        for (i, text) in text.lines().enumerate() {
+            let text = text.trim();
+            let separator = text.chars().all(|c| "|-_ \t".contains(c));
+
+            if text.starts_with('#') || separator {
+                continue;
+            }
+
+            let line =
+                parse_line(text, lines.len() + 1).map_err(|e| format!("line {}: {e}", i + 1))?;
+            lines.push(line);
+        }
+
+        Ok(Self { lines })
+    }
+}
+
+fn parse_line(text: &str, number: usize) -> Result<Line, String> {
+    let (n, rest) = text
+        .split_once('.')
+        .ok_or("expected a line number, such as `1.`")?;
+
+    if n.trim().parse::<usize>().ok() != Some(number) {
+        return Err(format!("expected line number {number}"));
+    }
+
+    let rest = rest.trim_start();
+    let formula = rest.trim_start_matches(|c: char| c == '|' || c.is_whitespace());
+    let depth = rest[..rest.len() - formula.len()]
+        .chars()
+        .filter(|c| *c == '|')
+        .count();
+
+    let (formula, justification) = formula
+        .rsplit_once(':')
+        .ok_or("expected `formula : rule`")?;
+
+    let formula = match formula.trim() {
+        "⊥" | "_|_" => Instruction::False,
+        formula => Instruction::try_from(formula)?,
+    };
+
+    let justification = justification.trim();
+    let (inference, references) = justification
+        .split_once(char::is_whitespace)
+        .unwrap_or((justification, ""));
+
+    let inference = Inference::try_from(inference)?;
+    let references = references
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|r| !r.is_empty())
+        .map(parse_reference)
+        .collect::<Result<Vec<_>, String>>()?;
+
+    Ok(Line {
+        number,
+        depth,
+        formula,
+        inference,
+        references,
+    })
+}
+
+fn parse_reference(reference: &str) -> Result<Reference, String> {
+    let number = |n: &str| {
+        n.parse::<usize>()
+            .map_err(|_| format!("invalid reference `{reference}`"))
+    };
+
+    match reference.split_once('-') {
+        Some((a, b)) => Ok(Reference::Subproof(number(a)?, number(b)?)),
+        None => Ok(Reference::Line(number(reference)?)),
+    }
+}
+
+impl Proof {
+    pub fn lines(&self) -> &[Line] {
+        &self.lines
+    }
+
+    /// The argument the proof establishes, from its premises to its last line.
+    pub fn entailment(&self) -> Option<Entailment> {
+        let conclusion = self.lines.last()?.formula.clone();
+        let premises = self
+            .lines
+            .iter()
+            .filter(|l| l.inference == Inference::Premise)
+            .map(|l| l.formula.clone())
+            .collect();
+
+        Some(Entailment::new(premises, conclusion))
+    }
+
+    /// Check every step of the proof.
+    ///
+    /// Fails with the number of the first invalid line and the reason.
+    pub fn check(&self) -> Result<(), String> {
+        // the first lines of the subproofs that contain each line, outermost first
+        let mut scopes: Vec<Vec<usize>> = vec![];
+        // the last line of every closed subproof, by its first line
+        let mut closed = HashMap::new();
+        let mut open: Vec<usize> = vec![];
+
+        for (i, line) in self.lines.iter().enumerate() {
+            let n = line.number;
+            let fail = |reason: String| Err(format!("line {n}: {reason}"));
+
+            let depth = match line.inference {
+                Inference::Assumption if line.depth == 0 => {
+                    return fail("an assumption must open a subproof".to_string());
+                }
+                Inference::Assumption if line.depth > open.len() + 1 => {
+                    return fail("a subproof can only be nested in the current one".to_string());
+                }
+                Inference::Assumption => line.depth - 1,
+                _ if line.depth > open.len() => {
+                    return fail("a subproof must start with an assumption".to_string());
+                }
+                _ => line.depth,
+            };
+
+            // a subproof only ends on the previous line if that line is not nested deeper in it
+            for (k, start) in open.drain(depth..).enumerate() {
+                if self.lines[i - 1].depth == depth + k + 1 {
+                    closed.insert(start, n - 1);
+                }
+            }
+
+            if line.inference == Inference::Assumption {
+                open.push(n);
+            }
+
+            if line.inference == Inference::Premise
+                && (line.depth > 0 || i > 0 && self.lines[i - 1].inference != Inference::Premise)
+            {
+                return fail("the premises must come before every other line".to_string());
+            }
+
+            let (expected_lines, expected_subproofs) = line.inference.arity();
+            let mut lines = vec![];
+            let mut subproofs = vec![];
+
+            for reference in &line.references {
+                match *reference {
+                    Reference::Line(j) if j == 0 || j >= n => {
+                        return fail(format!("line {j} is not a previous line"));
+                    }
+                    Reference::Line(j) if !open.starts_with(&scopes[j - 1]) => {
+                        return fail(format!("line {j} is inside a closed subproof"));
+                    }
+                    Reference::Line(j) => lines.push(&self.lines[j - 1].formula),
+
+                    Reference::Subproof(a, b) if closed.get(&a) != Some(&b) => {
+                        return fail(format!("{a}-{b} is not a closed subproof"));
+                    }
+                    Reference::Subproof(a, b)
+                        if self.lines[b - 1].depth > self.lines[a - 1].depth =>
+                    {
+                        return fail(format!("line {b} is nested deeper than {a}-{b}"));
+                    }
+                    Reference::Subproof(a, b) => {
+                        let parent = &scopes[a - 1][..scopes[a - 1].len() - 1];
+
+                        if !open.starts_with(parent) {
+                            return fail(format!("{a}-{b} is inside a closed subproof"));
+                        }
+
+                        subproofs.push((&self.lines[a - 1].formula, &self.lines[b - 1].formula));
+                    }
+                }
+            }
+
+            if lines.len() != expected_lines || subproofs.len() != expected_subproofs {
+                return fail(format!(
+                    "{} cites {expected_lines} lines and {expected_subproofs} subproofs, but {} and {} were given",
+                    line.inference,
+                    lines.len(),
+                    subproofs.len()
+                ));
+            }
+
+            if !line.inference.derives(&line.formula, &lines, &subproofs) {
+                return fail(format!(
+                    "{} does not derive {}: {}",
+                    line.inference,
+                    line.formula,
+                    line.inference.description()
+                ));
+            }
+
+            scopes.push(open.clone());
+        }
+
+        match self.lines.last() {
+            None => Err("the proof is empty".to_string()),
+            Some(line) if line.depth > 0 => Err(format!(
+                "line {}: the proof ends inside a subproof",
+                line.number
+            )),
+            Some(_) => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,158 @@
+use super::*;
+
+const MODUS_TOLLENS: &str = "
+# modus tollens
+1. p -> q   : premise
+2. !q       : premise
+3. | p      : assumption
+   | ---
+4. | q      : ->E 1, 3
+5. | _|_    : !E 4, 2
+6. !p       : ¬I 3-5
+";
+
+const DISJUNCTION_COMMUTES: &str = "
+1. p v q        : premise
+2. | p          : hyp
+3. | q v p      : ∨I 2
+4. | q          : hyp
+5. | q v p      : vI 4
+6. q v p        : ∨E 1, 2-3, 4-5
+";
+
+const EXCLUDED_MIDDLE: &str = "
+1. | !(p v !p)      : assumption
+2. | | p            : assumption
+3. | | p v !p       : vI 2
+4. | | ⊥            : ¬E 3, 1
+5. | !p             : ¬I 2-4
+6. | p v !p         : vI 5
+7. | ⊥              : ¬E 6, 1
+8. p v !p           : RAA 1-7
+";
+
+const BICONDITIONAL: &str = "
+1. p ^ q            : premise
+2. | p              : assumption
+3. | q              : ∧E 1
+4. | q              : assumption
+5. | p              : ^E 1
+6. p <-> q          : ↔I 2-3, 4-5
+7. q                : <->E 6, 5
+";
+
+fn check(text: &str) -> Result<(), String> {
+    Proof::try_from(text)?.check()
+}
+
+#[test]
+fn valid_proofs() {
+    for proof in [
+        MODUS_TOLLENS,
+        DISJUNCTION_COMMUTES,
+        EXCLUDED_MIDDLE,
+        "1. p : premise\n2. q : premise\n3. q ^ p : ∧I 2, 1\n4. p : reit 1",
+        "1. !(!p) : premise\n2. p : dne 1",
+        "1. !!p : premise\n2. p : ¬¬E 1",
+        "1. p : pr\n2. !p : pr\n3. _|_ : ⊥I 1, 2\n4. q : ⊥E 3",
+        "1. | p : as\n2. | p : R 1\n3. p -> p : →I 1-2",
+    ] {
+        let proof = Proof::try_from(proof).unwrap();
+
+        assert_eq!(Ok(()), proof.check(), "{proof:?}");
+        assert!(proof.entailment().unwrap().check().is_valid());
+    }
+}
+
+#[test]
+fn parse_proof() {
+    let proof = Proof::try_from(MODUS_TOLLENS).unwrap();
+    let line = &proof.lines()[4];
+
+    assert_eq!(6, proof.lines().len());
+    assert_eq!(5, line.number);
+    assert_eq!(1, line.depth);
+    assert_eq!(Instruction::False, line.formula);
+    assert_eq!(Inference::NotElim, line.inference);
+    assert_eq!(
+        vec![Reference::Line(4), Reference::Line(2)],
+        line.references
+    );
+    assert_eq!(vec![Reference::Subproof(3, 5)], proof.lines()[5].references);
+
+    assert_eq!(
+        "(\"p\" -> \"q\"), !\"q\" |= !\"p\"",
+        proof.entailment().unwrap().to_string()
+    );
+
+    assert_eq!(
+        Err("line 2: expected line number 2".to_string()),
+        Proof::try_from("1. p : premise\n3. p : R 1")
+    );
+    assert_eq!(
+        Err("line 1: unknown rule `MT`".to_string()),
+        Proof::try_from("1. p : MT 1")
+    );
+    assert!(Proof::try_from("1. p").is_err());
+}
+
+#[test]
+fn first_invalid_step() {
+    let cases = [
+        (
+            "1. p -> q : premise\n2. q : premise\n3. p : ->E 1, 2",
+            "line 3: →E does not derive \"p\": it derives `q` from `p -> q` and `p`",
+        ),
+        (
+            "1. p : premise\n2. | q : assumption\n3. p ^ q : ^I 1, 2\n4. p ^ q : R 3",
+            "line 3: line 2 is inside a closed subproof",
+        ),
+        (
+            "1. | p : assumption\n2. | p : R 1",
+            "line 2: the proof ends inside a subproof",
+        ),
+        (
+            "1. p : premise\n2. | p : R 1",
+            "line 2: a subproof must start with an assumption",
+        ),
+        (
+            "1. p : premise\n2. q : premise\n3. p ^ q : ^I 1",
+            "line 3: ∧I cites 2 lines and 0 subproofs, but 1 and 0 were given",
+        ),
+        (
+            "1. | p : assumption\n2. | p : R 1\n3. p -> p : ->I 1-3",
+            "line 3: 1-3 is not a closed subproof",
+        ),
+        (
+            "1. p : premise\n2. p : R 2",
+            "line 2: line 2 is not a previous line",
+        ),
+        (
+            "1. p : premise\n2. q v p : vI 1\n3. q : premise",
+            "line 3: the premises must come before every other line",
+        ),
+        (
+            "1. p v q : premise\n2. | p : as\n3. | p : R 2\n4. | q : as\n5. | p : R 2\n6. p : vE 1, 2-3, 4-5",
+            "line 5: line 2 is inside a closed subproof",
+        ),
+        (
+            "1. | p : assumption\n2. | | q : assumption\n3. | | q : R 2\n4. p -> q : ->I 1-3",
+            "line 4: 1-3 is not a closed subproof",
+        ),
+        (
+            "1. | !q : assumption\n2. | | q : assumption\n3. | | ⊥ : ¬E 2, 1\n4. q : RAA 1-3",
+            "line 4: 1-3 is not a closed subproof",
+        ),
+    ];
+
+    for (proof, error) in cases {
+        assert_eq!(Err(error.to_string()), check(proof), "{proof}");
+    }
+
+    // the conjunction is cited instead of one side of the biconditional
+    let proof = BICONDITIONAL.replace("<->E 6, 5", "<->E 6, 1");
+    assert_eq!(
+        Err("line 7: ↔E does not derive \"q\": it derives `q` from `p <-> q` and `p`, or `p` from `q`".to_string()),
+        check(&proof)
+    );
+}
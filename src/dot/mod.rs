@@ -0,0 +1,161 @@
+use super::*;
+use std::{collections::HashMap, fmt::Write as _};
+use traverser::Arguments;
+
+#[cfg(test)]
+mod tests;
+
+/// Graphviz DOT renderer of an instruction tree.
+///
+/// Every operator and argument is a node; binary operators link to their operands with edges
+/// labeled `left` and `right`.
+pub struct Dot {
+    graph: String,
+    nodes: usize,
+    /// The value of every node, by number, if the rendering is annotated.
+    values: Option<Vec<bool>>,
+}
+
+impl Dot {
+    /// Render the instruction without annotations.
+    pub fn render(instruction: &Instruction) -> String {
+        let mut dot = Dot {
+            graph: String::new(),
+            nodes: 0,
+            values: None,
+        };
+
+        dot.run(instruction);
+        dot.graph
+    }
+
+    /// Render the instruction, annotating every node with its value under the provided
+    /// assignment.
+    pub fn render_with_values(
+        instruction: &Instruction,
+        values: &[(&str, bool)],
+    ) -> Result<String, String> {
+        if let Some(arg) =
+            Arguments::from(instruction).find(|a| !values.iter().any(|(k, _)| k == a))
+        {
+            return Err(format!("the argument `{arg}` has no assigned value"));
+        }
+
+        let context = values.iter().copied().collect::<HashMap<_, _>>();
+        let mut values = vec![];
+        Self::evaluate(instruction, &context, &mut values);
+
+        let mut dot = Dot {
+            graph: String::new(),
+            nodes: 0,
+            values: Some(values),
+        };
+
+        dot.run(instruction);
+        Ok(dot.graph)
+    }
+
+    /// Evaluate every node once, bottom-up, storing the values in the order the nodes are
+    /// numbered.
+    fn evaluate(
+        instruction: &Instruction,
+        context: &HashMap<&str, bool>,
+        values: &mut Vec<bool>,
+    ) -> bool {
+        let id = values.len();
+        values.push(false);
+
+        use Instruction::*;
+        let value = match instruction {
+            True => true,
+            False => false,
+            Argument(a) => context[a.as_str()],
+            Not(x) => !Self::evaluate(x, context, values),
+
+            And(l, r)
+            | Or(l, r)
+            | Xor(l, r)
+            | Conditional(l, r)
+            | Biconditional(l, r)
+            | Equals(l, r) => {
+                let l = Self::evaluate(l, context, values);
+                let r = Self::evaluate(r, context, values);
+
+                match instruction {
+                    And(_, _) => l && r,
+                    Or(_, _) => l || r,
+                    Xor(_, _) => l != r,
+                    Conditional(_, _) => !l || r,
+                    _ => l == r,
+                }
+            }
+        };
+
+        values[id] = value;
+        value
+    }
+
+    fn run(&mut self, instruction: &Instruction) {
+        self.graph.push_str("digraph instruction {\n");
+        self.graph.push_str("    node [fontname=\"monospace\"];\n");
+        self.node(instruction);
+        self.graph.push_str("}\n");
+    }
+
+    fn node(&mut self, instruction: &Instruction) -> usize {
+        let id = self.nodes;
+        self.nodes += 1;
+
+        use Instruction::*;
+        let (label, shape) = match instruction {
+            True => ("1".to_string(), "box"),
+            False => ("0".to_string(), "box"),
+            Argument(a) => (a.replace('\\', "\\\\").replace('"', "\\\""), "box"),
+            Not(_) => ("!".to_string(), "circle"),
+            And(_, _) => ("^".to_string(), "circle"),
+            Or(_, _) => ("v".to_string(), "circle"),
+            Xor(_, _) => ("+".to_string(), "circle"),
+            Conditional(_, _) => ("->".to_string(), "circle"),
+            Biconditional(_, _) => ("<->".to_string(), "circle"),
+            Equals(_, _) => ("=".to_string(), "circle"),
+        };
+
+        let value = self.values.as_ref().map(|values| values[id]);
+
+        match value {
+            Some(v) => writeln!(
+                self.graph,
+                "    n{id} [label=\"{label}\\n{}\", shape={shape}];",
+                u8::from(v)
+            ),
+            None => writeln!(self.graph, "    n{id} [label=\"{label}\", shape={shape}];"),
+        }
+        .expect("writing to a string is infallible");
+
+        match instruction {
+            True | False | Argument(_) => (),
+
+            Not(x) => {
+                let x = self.node(x);
+                writeln!(self.graph, "    n{id} -> n{x};")
+                    .expect("writing to a string is infallible");
+            }
+
+            And(l, r)
+            | Or(l, r)
+            | Xor(l, r)
+            | Conditional(l, r)
+            | Biconditional(l, r)
+            | Equals(l, r) => {
+                let l = self.node(l);
+                let r = self.node(r);
+                writeln!(self.graph, "    n{id} -> n{l} [label=\"left\"];")
+                    .expect("writing to a string is infallible");
+                writeln!(self.graph, "    n{id} -> n{r} [label=\"right\"];")
+                    .expect("writing to a string is infallible");
+            }
+        }
+
+        id
+    }
+}
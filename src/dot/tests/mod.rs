@@ -0,0 +1,65 @@
+use super::*;
+
+#[test]
+fn render_tree() {
+    let instruction = Instruction::try_from("!a v b").unwrap();
+    let expected = r#"digraph instruction {
+    node [fontname="monospace"];
+    n0 [label="v", shape=circle];
+    n1 [label="!", shape=circle];
+    n2 [label="a", shape=box];
+    n1 -> n2;
+    n3 [label="b", shape=box];
+    n0 -> n1 [label="left"];
+    n0 -> n3 [label="right"];
+}
+"#;
+
+    assert_eq!(expected, instruction.to_dot());
+}
+
+#[test]
+fn render_tree_with_values() {
+    let instruction = Instruction::try_from("a ^ 'x y'").unwrap();
+    let expected = r#"digraph instruction {
+    node [fontname="monospace"];
+    n0 [label="^\n0", shape=circle];
+    n1 [label="a\n1", shape=box];
+    n2 [label="x y\n0", shape=box];
+    n0 -> n1 [label="left"];
+    n0 -> n2 [label="right"];
+}
+"#;
+
+    let dot = instruction
+        .to_dot_with_values(&[("a", true), ("x y", false)])
+        .unwrap();
+
+    assert_eq!(expected, dot);
+}
+
+#[test]
+fn render_tree_with_unbound_argument() {
+    let instruction = Instruction::try_from("a ^ b").unwrap();
+
+    assert!(instruction.to_dot_with_values(&[("a", true)]).is_err());
+}
+
+#[test]
+fn render_nested_values() {
+    let instruction = Instruction::try_from("!(a -> b) = c").unwrap();
+    let dot = instruction
+        .to_dot_with_values(&[("a", true), ("b", false), ("c", true)])
+        .unwrap();
+
+    for node in [
+        "n0 [label=\"=\\n1\"",
+        "n1 [label=\"!\\n1\"",
+        "n2 [label=\"->\\n0\"",
+        "n3 [label=\"a\\n1\"",
+        "n4 [label=\"b\\n0\"",
+        "n5 [label=\"c\\n1\"",
+    ] {
+        assert!(dot.contains(node), "{node} in {dot}");
+    }
+}
@@ -0,0 +1,196 @@
+use super::*;
+use std::fmt;
+
+#[cfg(test)]
+mod tests;
+
+/// Argument from premises to a conclusion, written `p -> q, p |= q`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Entailment {
+    pub premises: Vec<Instruction>,
+    pub conclusion: Instruction,
+}
+
+/// Invalid argument shape that is commonly mistaken for a valid one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Fallacy {
+    /// `p -> q, q |= p`
+    AffirmingTheConsequent,
+    /// `p -> q, !p |= !q`
+    DenyingTheAntecedent,
+    /// `p v q, p |= !q`
+    AffirmingADisjunct,
+    /// `!(p ^ q), !p |= q`
+    DenyingAConjunct,
+    /// `p -> q |= q -> p`
+    CommutingAConditional,
+}
+
+impl fmt::Display for Fallacy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::AffirmingTheConsequent => "affirming the consequent",
+            Self::DenyingTheAntecedent => "denying the antecedent",
+            Self::AffirmingADisjunct => "affirming a disjunct",
+            Self::DenyingAConjunct => "denying a conjunct",
+            Self::CommutingAConditional => "commuting a conditional",
+        };
+
+        write!(f, "{name}")
+    }
+}
+
+/// Result of checking an [`Entailment`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Verdict {
+    /// Every assignment that satisfies the premises satisfies the conclusion.
+    Valid,
+    /// The countermodel satisfies every premise and falsifies the conclusion. The fallacy is
+    /// named if the argument has one of the known shapes.
+    Invalid {
+        countermodel: Model,
+        fallacy: Option<Fallacy>,
+    },
+}
+
+impl Verdict {
+    pub const fn is_valid(&self) -> bool {
+        matches!(self, Self::Valid)
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Valid => write!(f, "valid"),
+            Self::Invalid {
+                countermodel,
+                fallacy,
+            } => {
+                write!(f, "invalid: {countermodel} is a countermodel")?;
+
+                match fallacy {
+                    Some(fallacy) => write!(f, " ({fallacy})"),
+                    None => Ok(()),
+                }
+            }
+        }
+    }
+}
+
+impl Entailment {
+    pub fn new(premises: Vec<Instruction>, conclusion: Instruction) -> Self {
+        Self {
+            premises,
+            conclusion,
+        }
+    }
+
+    /// The conjunction of the premises, or `True` if there are none.
+    pub fn antecedent(&self) -> Instruction {
+        self.premises
+            .iter()
+            .cloned()
+            .reduce(|l, r| Instruction::And(Box::new(l), Box::new(r)))
+            .unwrap_or(Instruction::True)
+    }
+
+    /// Decide whether the premises entail the conclusion, by searching for an assignment that
+    /// satisfies the premises and falsifies the conclusion.
+    pub fn check(&self) -> Verdict {
+        self.check_assuming(vec![])
+    }
+
+    /// Decide whether the premises entail the conclusion, with the assumptions as additional
+    /// premises. The fallacy is named from the shape of the argument alone.
+    pub fn check_assuming(&self, assumptions: Vec<Instruction>) -> Verdict {
+        let antecedent = assumptions.into_iter().fold(self.antecedent(), |l, r| {
+            Instruction::And(Box::new(l), Box::new(r))
+        });
+
+        let counter = Instruction::And(
+            Box::new(antecedent),
+            Box::new(Instruction::Not(Box::new(self.conclusion.clone()))),
+        );
+
+        match counter.satisfy() {
+            None => Verdict::Valid,
+            Some(countermodel) => Verdict::Invalid {
+                countermodel,
+                fallacy: self.fallacy(),
+            },
+        }
+    }
+
+    /// Prove that the premises entail the conclusion with a resolution refutation of the premises
+    /// and the negated conclusion.
+    ///
+    /// Returns `None` if the argument is invalid, and fails if the refutation is too large to
+    /// print.
+    pub fn prove(&self) -> Result<Option<Refutation>, String> {
+        let mut inputs = self
+            .premises
+            .iter()
+            .enumerate()
+            .map(|(i, p)| (format!("premise {}", i + 1), p.clone()))
+            .collect::<Vec<_>>();
+
+        let negation = Instruction::Not(Box::new(self.conclusion.clone()));
+        inputs.push(("negated conclusion".to_string(), negation));
+
+        Refutation::prove(inputs)
+    }
+
+    /// The fallacy whose shape matches the argument, regardless of the order of the premises.
+    pub fn fallacy(&self) -> Option<Fallacy> {
+        use Instruction::*;
+        let c = &self.conclusion;
+
+        let orders = match self.premises.as_slice() {
+            [Conditional(p, q)] => {
+                let commuted = matches!(c, Conditional(r, s) if r == q && s == p);
+                return commuted.then_some(Fallacy::CommutingAConditional);
+            }
+            [a, b] => [(a, b), (b, a)],
+            _ => return None,
+        };
+
+        orders
+            .into_iter()
+            .find_map(|(major, minor)| match (major, minor, c) {
+                (Conditional(p, q), m, _) if m == &**q && c == &**p => {
+                    Some(Fallacy::AffirmingTheConsequent)
+                }
+                (Conditional(p, q), Not(m), Not(r)) if m == p && r == q => {
+                    Some(Fallacy::DenyingTheAntecedent)
+                }
+                (Or(p, q), m, Not(r)) if (m == &**p && r == q) || (m == &**q && r == p) => {
+                    Some(Fallacy::AffirmingADisjunct)
+                }
+                (Not(x), Not(m), _) => match &**x {
+                    And(p, q) if (m == p && c == &**q) || (m == q && c == &**p) => {
+                        Some(Fallacy::DenyingAConjunct)
+                    }
+                    _ => None,
+                },
+                _ => None,
+            })
+    }
+}
+
+impl fmt::Display for Entailment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let premises = self
+            .premises
+            .iter()
+            .map(|p| p.to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        if premises.is_empty() {
+            write!(f, "|= {}", self.conclusion)
+        } else {
+            write!(f, "{premises} |= {}", self.conclusion)
+        }
+    }
+}
@@ -0,0 +1,93 @@
+use super::*;
+
+fn check(argument: &str) -> Verdict {
+    Entailment::try_from(argument).unwrap().check()
+}
+
+fn fallacy(argument: &str) -> Option<Fallacy> {
+    match check(argument) {
+        Verdict::Invalid { fallacy, .. } => fallacy,
+        Verdict::Valid => panic!("{argument} is valid"),
+    }
+}
+
+#[test]
+fn parse_entailment() {
+    let entailment = Entailment::try_from("p -> q, p |= q").unwrap();
+
+    assert_eq!(
+        vec![
+            Instruction::try_from("p -> q").unwrap(),
+            Instruction::try_from("p").unwrap()
+        ],
+        entailment.premises
+    );
+    assert_eq!(Instruction::try_from("q").unwrap(), entailment.conclusion);
+
+    let entailment = Entailment::try_from(" |= p v !p").unwrap();
+    assert!(entailment.premises.is_empty());
+
+    assert!(Entailment::try_from("p, q").is_err());
+    assert!(Entailment::try_from("p |= ").is_err());
+}
+
+#[test]
+fn valid_arguments() {
+    assert!(check("p -> q, p |= q").is_valid());
+    assert!(check("p -> q, !q |= !p").is_valid());
+    assert!(check("p v q, !p |= q").is_valid());
+    assert!(check("p -> q, q -> r |= p -> r").is_valid());
+    assert!(check("|= p v !p").is_valid());
+    assert!(check("p, !p |= q").is_valid());
+}
+
+#[test]
+fn countermodel() {
+    let entailment = Entailment::try_from("p -> q, q |= p").unwrap();
+
+    let Verdict::Invalid { countermodel, .. } = entailment.check() else {
+        panic!("the argument is invalid");
+    };
+
+    for premise in &entailment.premises {
+        assert_eq!(Ok(true), premise.evaluate_with(&countermodel));
+    }
+    assert_eq!(
+        Ok(false),
+        entailment.conclusion.evaluate_with(&countermodel)
+    );
+}
+
+#[test]
+fn name_fallacies() {
+    use Fallacy::*;
+
+    assert_eq!(Some(AffirmingTheConsequent), fallacy("p -> q, q |= p"));
+    assert_eq!(
+        Some(AffirmingTheConsequent),
+        fallacy("q, (a ^ b) -> q |= a ^ b")
+    );
+    assert_eq!(Some(DenyingTheAntecedent), fallacy("p -> q, !p |= !q"));
+    assert_eq!(Some(AffirmingADisjunct), fallacy("p v q, q |= !p"));
+    assert_eq!(Some(DenyingAConjunct), fallacy("!(p ^ q), !p |= q"));
+    assert_eq!(Some(CommutingAConditional), fallacy("p -> q |= q -> p"));
+    assert_eq!(None, fallacy("p v q |= p"));
+
+    assert_eq!(
+        "invalid: p=0, q=1 is a countermodel (affirming the consequent)",
+        check("p -> q, q |= p").to_string()
+    );
+}
+
+#[test]
+fn check_assuming() {
+    let entailment = Entailment::try_from("p -> q, q |= p").unwrap();
+    let r = Instruction::Argument("r".to_string());
+    let p = Instruction::Argument("p".to_string());
+
+    assert_eq!(
+        "invalid: p=0, q=1, r=1 is a countermodel (affirming the consequent)",
+        entailment.check_assuming(vec![r]).to_string()
+    );
+    assert!(entailment.check_assuming(vec![p]).is_valid());
+}
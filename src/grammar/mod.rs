@@ -1,4 +1,4 @@
-use super::Instruction;
+use super::{Entailment, Instruction};
 use core::fmt;
 use inner::Rule;
 use pest::{
@@ -15,6 +15,7 @@ mod inner {
     pub struct Parser;
 }
 
+/// Parse the expression tree as written, without optimizing it.
 impl TryFrom<&str> for Instruction {
     type Error = String;
 
@@ -25,26 +26,96 @@ impl TryFrom<&str> for Instruction {
         let pair = fetch_pair(&mut pairs)?;
         let expr = fetch_pair(&mut pair.into_inner())?;
 
-        fetch_expr(&mut expr.into_inner()).map(Self::optimize)
+        fetch_expr(&mut expr.into_inner())
     }
 }
 
+impl TryFrom<&str> for Entailment {
+    type Error = String;
+
+    fn try_from(program: &str) -> Result<Self, Self::Error> {
+        let mut pairs =
+            inner::Parser::parse(Rule::entailment, program).map_err(|e| e.to_string())?;
+
+        let mut pairs = fetch_pair(&mut pairs)?.into_inner();
+        let premises = fetch_pair(&mut pairs)?
+            .into_inner()
+            .map(|expr| fetch_expr(&mut expr.into_inner()))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let conclusion = fetch_pair(&mut pairs)?;
+        let conclusion = fetch_expr(&mut conclusion.into_inner())?;
+
+        Ok(Entailment::new(premises, conclusion))
+    }
+}
+
+/// Parse a comma separated list of expressions, paired with the text of each one.
+pub fn list(program: &str) -> Result<Vec<(&str, Instruction)>, String> {
+    let mut pairs = inner::Parser::parse(Rule::list, program).map_err(|e| e.to_string())?;
+
+    let mut pairs = fetch_pair(&mut pairs)?.into_inner();
+    fetch_pair(&mut pairs)?
+        .into_inner()
+        .map(|expr| Ok((expr.as_str(), fetch_expr(&mut expr.into_inner())?)))
+        .collect()
+}
+
+/// The alternate form, `{:#}`, leaves the names that parse as plain identifiers unquoted and
+/// omits the outermost parentheses, such as `a ^ (b v "flag one")`.
 impl fmt::Display for Instruction {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        use Instruction::*;
-        match self {
-            True => write!(f, "1"),
-            False => write!(f, "0"),
-            Argument(a) => write!(f, "\"{a}\""),
-            Not(i) => write!(f, "!{i}"),
-            And(l, r) => write!(f, "({l} ^ {r})"),
-            Or(l, r) => write!(f, "({l} v {r})"),
-            Xor(l, r) => write!(f, "({l} + {r})"),
-            Conditional(l, r) => write!(f, "({l} -> {r})"),
-            Biconditional(l, r) => write!(f, "({l} <-> {r})"),
-            Equals(l, r) => write!(f, "({l} = {r})"),
+        let plain = f.alternate();
+        write_instruction(f, self, plain, plain)
+    }
+}
+
+fn write_instruction(
+    f: &mut fmt::Formatter<'_>,
+    instruction: &Instruction,
+    plain: bool,
+    top: bool,
+) -> fmt::Result {
+    use Instruction::*;
+    let (op, l, r) = match instruction {
+        True => return write!(f, "1"),
+        False => return write!(f, "0"),
+        Argument(a) if plain && is_plain(a) => return write!(f, "{a}"),
+        Argument(a) => return write!(f, "\"{a}\""),
+        Not(x) => {
+            write!(f, "!")?;
+            return write_instruction(f, x, plain, false);
         }
+        And(l, r) => ("^", l, r),
+        Or(l, r) => ("v", l, r),
+        Xor(l, r) => ("+", l, r),
+        Conditional(l, r) => ("->", l, r),
+        Biconditional(l, r) => ("<->", l, r),
+        Equals(l, r) => ("=", l, r),
+    };
+
+    if !top {
+        write!(f, "(")?;
     }
+
+    write_instruction(f, l, plain, false)?;
+    write!(f, " {op} ")?;
+    write_instruction(f, r, plain, false)?;
+
+    if !top {
+        write!(f, ")")?;
+    }
+
+    Ok(())
+}
+
+/// Whether the name reads back as the same argument without quotes.
+fn is_plain(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_alphabetic())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+        && Instruction::try_from(name) == Ok(Instruction::Argument(name.to_string()))
 }
 
 fn fetch_pair<'a>(pairs: &mut Pairs<'a, Rule>) -> Result<Pair<'a, Rule>, String> {
@@ -111,7 +182,7 @@ fn fetch_expr(pairs: &mut Pairs<'_, Rule>) -> Result<Instruction, String> {
     let pair = fetch_pair(pairs)?;
     let mut term = fetch_term(&mut pair.into_inner())?;
 
-    while let Some(infix) = fetch_infix_rule(pairs)? {
+    while let Some(rule) = fetch_infix_rule(pairs)? {
         let lhs = Box::new(term);
 
         let rhs = fetch_pair(pairs)?;
@@ -119,7 +190,7 @@ fn fetch_expr(pairs: &mut Pairs<'_, Rule>) -> Result<Instruction, String> {
         let rhs = Box::new(rhs);
 
         use Instruction::*;
-        term = match infix {
+        term = match rule {
             Rule::and => And(lhs, rhs),
             Rule::or => Or(lhs, rhs),
             Rule::xor => Xor(lhs, rhs),
@@ -63,3 +63,44 @@ fn parse_complex() {
         Instruction::try_from("!(a + b) = !c -> x").unwrap()
     );
 }
+
+#[test]
+fn parse_list() {
+    let a = Argument("a".to_string());
+    let bc = Argument("b,c".to_string());
+    let and = And(Box::new(a.clone()), Box::new(bc.clone()));
+
+    assert_eq!(
+        vec![("a ^ 'b,c'", and), ("a", a), ("!'b,c'", Not(Box::new(bc)))],
+        list(" a ^ 'b,c',a , !'b,c' ").unwrap()
+    );
+
+    assert!(list("a, ").is_err());
+    assert!(list("").is_err());
+}
+
+#[test]
+fn parse_without_optimizing() {
+    let a = Argument("a".to_string());
+    let b = Argument("b".to_string());
+    let expected = Or(
+        Box::new(a.clone()),
+        Box::new(And(Box::new(a.clone()), Box::new(b))),
+    );
+
+    let instruction = Instruction::try_from("a or (a and b)").unwrap();
+    assert_eq!(expected, instruction);
+    assert_eq!(a, instruction.optimize());
+}
+
+#[test]
+fn display_plain_names() {
+    let instruction = Instruction::try_from("!a v (b ^ 'x y') -> 'v'").unwrap();
+
+    assert_eq!(
+        "((!\"a\" v (\"b\" ^ \"x y\")) -> \"v\")",
+        instruction.to_string()
+    );
+    assert_eq!("(!a v (b ^ \"x y\")) -> \"v\"", format!("{instruction:#}"));
+    assert_eq!("a", format!("{:#}", Argument("a".into())));
+}
@@ -122,9 +122,7 @@ where
 
         result.iter_mut().fold(0, |mut j, r| {
             if j == len {
-                let y = pair[0];
-                pair[0] = pair[1];
-                pair[1] = y;
+                pair.swap(0, 1);
                 j = 1;
             } else {
                 j += 1;
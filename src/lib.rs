@@ -1,10 +1,46 @@
+pub use allsat::{Cube, Cubes};
+pub use assignment::Assignment;
+pub use bitwise::Rows;
+pub use bytecode::{Op, Program, Word};
+pub use classify::{Certificate, Classification};
+pub use codegen::Language;
 use context::Context;
+pub use deduction::{Inference, Line, Proof, Reference};
+pub use entailment::{Entailment, Fallacy, Verdict};
+pub use mus::{Constraints, Explorer, Subset};
+pub use num_bigint::BigUint;
+pub use resolution::{Clause, Justification, Refutation, Step};
+pub use sat::Model;
 use std::{iter, ops::Deref};
-
+pub use table::{ColumnOrder, Filter, RowOrder, TableOptions};
+pub use tableau::{Leaf, Node, Tableau};
+
+mod allsat;
+mod assignment;
+mod backbone;
+mod binary;
+mod bitwise;
+mod bytecode;
+mod circuit;
+mod classify;
+mod cnf;
+mod codegen;
 mod context;
+mod count;
+mod deduction;
+mod dot;
+mod entailment;
 mod grammar;
+mod mus;
+mod resolution;
+mod sat;
+mod table;
+mod tableau;
 mod traverser;
 
+#[cfg(test)]
+mod tests;
+
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum Instruction {
     True,
@@ -20,8 +56,165 @@ pub enum Instruction {
 }
 
 impl Instruction {
+    /// Parse a comma separated list of expressions, such as `a ^ b, b ^ a`, paired with the text
+    /// of each one. Commas inside quoted identifiers, such as `'b,c'`, do not separate them.
+    pub fn parse_list(program: &str) -> Result<Vec<(&str, Instruction)>, String> {
+        grammar::list(program)
+    }
+
     pub fn evaluate(&self) -> Result<Table, String> {
-        traverser::Evaluator::run(self).map(Evaluation::into_table)
+        self.table(&TableOptions::default())
+    }
+
+    /// Build a step-by-step truth table, with one column per distinct subexpression of the
+    /// instruction, ordered bottom-up.
+    pub fn steps(&self, options: &TableOptions) -> Result<Table, String> {
+        table::TableGenerator::steps(self, options)
+    }
+
+    /// Build a single truth table of several instructions over the union of their arguments, with
+    /// one result column per instruction and a final `differ` column.
+    pub fn compare(instructions: &[Instruction], options: &TableOptions) -> Result<Table, String> {
+        table::TableGenerator::compare(instructions, options)
+    }
+
+    /// Build the truth table with the provided column and row order.
+    pub fn table(&self, options: &TableOptions) -> Result<Table, String> {
+        table::TableGenerator::run(self, options)
+    }
+
+    /// Search for an assignment of the arguments that satisfies the instruction, without
+    /// enumerating its truth table.
+    ///
+    /// Returns `None` if the instruction is unsatisfiable.
+    pub fn satisfy(&self) -> Option<Model> {
+        sat::Sat::satisfy(self)
+    }
+
+    /// The backbone of the instruction: the values of the arguments that are the same in every
+    /// model, in the order of the arguments.
+    ///
+    /// Fails if the instruction is unsatisfiable.
+    pub fn backbone(&self) -> Result<Vec<(String, bool)>, String> {
+        backbone::Backbone::run(self, &|_: &str| None)
+    }
+
+    /// The values forced on the remaining arguments once the bound ones are fixed, in the order
+    /// of the arguments.
+    ///
+    /// Fails if no model of the instruction agrees with the assignment.
+    pub fn implied<A>(&self, assignment: &A) -> Result<Vec<(String, bool)>, String>
+    where
+        A: Assignment + ?Sized,
+    {
+        backbone::Backbone::run(self, assignment)
+    }
+
+    /// Lazily enumerate the models of the instruction as disjoint cubes, with don't-cares for the
+    /// arguments whose value does not matter.
+    pub fn cubes(&self) -> Cubes {
+        Cubes::new(self)
+    }
+
+    /// Count the assignments of the arguments that satisfy the instruction, without enumerating
+    /// them.
+    pub fn count_models(&self) -> BigUint {
+        count::Counter::count(self)
+    }
+
+    /// Search for an assignment of the arguments of both instructions under which they evaluate
+    /// to different values.
+    ///
+    /// Returns `None` if the instructions are equivalent. The search runs a SAT solver, so it
+    /// does not build the truth tables.
+    pub fn distinguish(&self, other: &Instruction) -> Option<Model> {
+        sat::Sat::distinguish(self, other)
+    }
+
+    /// Whether the instructions evaluate to the same value under every assignment.
+    pub fn is_equivalent(&self, other: &Instruction) -> bool {
+        self.distinguish(other).is_none()
+    }
+
+    /// Classify the instruction as a tautology, a contradiction or contingent.
+    ///
+    /// A tautology comes with a checkable refutation of its negation, and the other classes with
+    /// the assignments that witness them.
+    pub fn classify(&self) -> Classification {
+        Classification::run(self)
+    }
+
+    /// Prove that the instruction is a tautology with a resolution refutation of its negation.
+    ///
+    /// Returns `None` if the instruction is not a tautology, and fails if the refutation is too
+    /// large to print.
+    pub fn prove(&self) -> Result<Option<Refutation>, String> {
+        let negation = Instruction::Not(Box::new(self.clone()));
+
+        Refutation::prove(vec![("negated formula".to_string(), negation)])
+    }
+
+    /// Expand the analytic tableau of the negation of the instruction.
+    ///
+    /// The tableau closes if and only if the instruction is a tautology; otherwise, the model of
+    /// an open branch falsifies it. Fails if the tableau is too large to print.
+    pub fn tableau(&self) -> Result<Tableau, String> {
+        Tableau::new(vec![Instruction::Not(Box::new(self.clone()))])
+    }
+
+    /// Evaluate the instruction for a single assignment of its arguments.
+    ///
+    /// Fails if any of the arguments is unbound.
+    pub fn evaluate_with<A>(&self, assignment: &A) -> Result<bool, String>
+    where
+        A: Assignment + ?Sized,
+    {
+        Program::compile(self).eval_with(assignment)
+    }
+
+    /// Compile the instruction into a stack machine program, with its arguments resolved to
+    /// slots in alphabetical order.
+    pub fn bytecode(&self) -> Program<'_> {
+        Program::compile(self)
+    }
+
+    /// Lazily evaluate the rows of the truth table, one at a time.
+    pub fn rows(&self) -> Result<Rows<'_>, String> {
+        Rows::new(self)
+    }
+
+    /// Evaluate the instruction for every assignment of its arguments, one at a time.
+    pub fn evaluations(&self) -> Result<Vec<Evaluation<'_>>, String> {
+        traverser::Evaluator::run(self)
+    }
+
+    pub fn to_dot(&self) -> String {
+        dot::Dot::render(self)
+    }
+
+    pub fn to_dot_with_values(&self, values: &[(&str, bool)]) -> Result<String, String> {
+        dot::Dot::render_with_values(self, values)
+    }
+
+    pub fn to_svg(&self) -> String {
+        circuit::Circuit::render(self)
+    }
+
+    /// Compile the instruction into a function named `name` of the target language.
+    pub fn compile(&self, language: Language, name: &str) -> String {
+        codegen::Codegen::compile(self, language, name)
+    }
+
+    /// Encode the instruction into its compact binary form.
+    ///
+    /// Fails if the instruction is nested too deep to be decoded.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, String> {
+        binary::Binary::encode(self)
+    }
+
+    /// Decode an instruction from its compact binary form, rejecting malformed input.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, String> {
+        binary::Binary::decode(bytes)
     }
 
     pub const fn eq_true(&self) -> bool {
@@ -300,6 +493,84 @@ impl Instruction {
             .de_morgan_reduction()
             ._optimize(ctx)
     }
+
+    fn negate(self) -> Self {
+        use Instruction::*;
+        match self {
+            True => False,
+            False => True,
+            x => Not(Box::new(x)),
+        }
+    }
+
+    fn substitute<A>(self, assignment: &A) -> Self
+    where
+        A: Assignment + ?Sized,
+    {
+        use Instruction::*;
+        match self {
+            True => True,
+            False => False,
+
+            Argument(a) => match assignment.value(&a) {
+                Some(true) => True,
+                Some(false) => False,
+                None => Argument(a),
+            },
+
+            Not(x) => x.substitute(assignment).negate(),
+
+            And(l, r) => match (l.substitute(assignment), r.substitute(assignment)) {
+                (False, _) | (_, False) => False,
+                (True, x) | (x, True) => x,
+                (l, r) => And(Box::new(l), Box::new(r)),
+            },
+
+            Or(l, r) => match (l.substitute(assignment), r.substitute(assignment)) {
+                (True, _) | (_, True) => True,
+                (False, x) | (x, False) => x,
+                (l, r) => Or(Box::new(l), Box::new(r)),
+            },
+
+            Xor(l, r) => match (l.substitute(assignment), r.substitute(assignment)) {
+                (False, x) | (x, False) => x,
+                (True, x) | (x, True) => x.negate(),
+                (l, r) => Xor(Box::new(l), Box::new(r)),
+            },
+
+            Conditional(l, r) => match (l.substitute(assignment), r.substitute(assignment)) {
+                (False, _) | (_, True) => True,
+                (True, x) => x,
+                (x, False) => x.negate(),
+                (l, r) => Conditional(Box::new(l), Box::new(r)),
+            },
+
+            Biconditional(l, r) => match (l.substitute(assignment), r.substitute(assignment)) {
+                (True, x) | (x, True) => x,
+                (False, x) | (x, False) => x.negate(),
+                (l, r) => Biconditional(Box::new(l), Box::new(r)),
+            },
+
+            Equals(l, r) => match (l.substitute(assignment), r.substitute(assignment)) {
+                (True, x) | (x, True) => x,
+                (False, x) | (x, False) => x.negate(),
+                (l, r) => Equals(Box::new(l), Box::new(r)),
+            },
+        }
+    }
+
+    /// Partially evaluate the instruction, returning the residual instruction over the arguments
+    /// that are unbound in the assignment.
+    ///
+    /// The bound arguments are replaced by constants, which are folded through every node before
+    /// the result is optimized.
+    pub fn assume<A>(self, assignment: &A) -> Self
+    where
+        A: Assignment + ?Sized,
+    {
+        self.substitute(assignment)
+            ._optimize(&mut Context::default())
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -327,6 +598,10 @@ impl<'a> Evaluation<'a> {
         self.result
     }
 
+    /// Build a table from the evaluations, keeping their order.
+    ///
+    /// The evaluator produces the rows in ascending binary order of the arguments, so no sorting
+    /// is performed.
     pub fn into_table(result: Vec<Self>) -> Table {
         let header = result
             .first()
@@ -340,7 +615,7 @@ impl<'a> Evaluation<'a> {
             })
             .unwrap_or_default();
 
-        let mut rows = result
+        let rows = result
             .into_iter()
             .map(|ev| {
                 ev.values
@@ -349,9 +624,7 @@ impl<'a> Evaluation<'a> {
                     .chain(iter::once(ev.result))
                     .collect()
             })
-            .collect::<Vec<_>>();
-
-        rows.as_mut_slice().sort();
+            .collect();
 
         Table { header, rows }
     }
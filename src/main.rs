@@ -4,7 +4,7 @@ use cli_table::{
 };
 use rustyline::{error::ReadlineError, Config as RustylineConfig, Editor};
 use serde::{Deserialize, Serialize};
-use std::fs;
+use std::{fs, io};
 use toml_base_config::BaseConfig;
 use truthful::*;
 
@@ -48,14 +48,73 @@ impl Default for Readline {
     }
 }
 
+/// Order of the argument columns of the truth tables
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TableColumns {
+    #[default]
+    Alphabetical,
+    Appearance,
+    Explicit(Vec<String>),
+}
+
+impl From<TableColumns> for ColumnOrder {
+    fn from(columns: TableColumns) -> Self {
+        match columns {
+            TableColumns::Alphabetical => ColumnOrder::Alphabetical,
+            TableColumns::Appearance => ColumnOrder::Appearance,
+            TableColumns::Explicit(names) => ColumnOrder::Explicit(names),
+        }
+    }
+}
+
+/// Order of the rows of the truth tables
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TableRows {
+    #[default]
+    Ascending,
+    Descending,
+    Gray,
+}
+
+impl From<TableRows> for RowOrder {
+    fn from(rows: TableRows) -> Self {
+        match rows {
+            TableRows::Ascending => RowOrder::Ascending,
+            TableRows::Descending => RowOrder::Descending,
+            TableRows::Gray => RowOrder::Gray,
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Options {
     pub cheesy_mode: bool,
+    #[serde(default)]
+    pub columns: TableColumns,
+    #[serde(default)]
+    pub rows: TableRows,
 }
 
 impl Default for Options {
     fn default() -> Self {
-        Self { cheesy_mode: true }
+        Self {
+            cheesy_mode: true,
+            columns: TableColumns::default(),
+            rows: TableRows::default(),
+        }
+    }
+}
+
+impl Options {
+    /// Create the layout options of the truth tables
+    pub fn table(&self) -> TableOptions {
+        TableOptions {
+            columns: self.columns.clone().into(),
+            rows: self.rows.into(),
+            filter: Filter::All,
+        }
     }
 }
 
@@ -77,12 +136,546 @@ impl BaseConfig for Config {
     const PACKAGE: &'static str = env!("CARGO_PKG_NAME");
 }
 
+/// State of the REPL that persists between lines
+#[derive(Debug, Default)]
+pub struct Session {
+    /// Values assumed for arguments, substituted into every evaluated expression
+    assumptions: Vec<(String, bool)>,
+}
+
+impl Session {
+    /// Apply the assumptions to the instruction and optimize it
+    pub fn prepare(&self, instruction: Instruction) -> Instruction {
+        if self.assumptions.is_empty() {
+            instruction.optimize()
+        } else {
+            instruction.assume(&self.assumptions).optimize()
+        }
+    }
+
+    /// Conjoin the assumptions to the instruction, without optimizing it
+    ///
+    /// Optimizing compares truth tables, so this is the preparation for expressions that may have
+    /// too many arguments to enumerate
+    pub fn constrain(&self, instruction: Instruction) -> Instruction {
+        self.literals().fold(instruction, |l, r| {
+            Instruction::And(Box::new(l), Box::new(r))
+        })
+    }
+
+    /// The assumptions as literals, such as `a` and `!b`
+    fn literals(&self) -> impl Iterator<Item = Instruction> + '_ {
+        self.assumptions.iter().map(|(k, v)| {
+            let argument = Instruction::Argument(k.clone());
+            if *v {
+                argument
+            } else {
+                Instruction::Not(Box::new(argument))
+            }
+        })
+    }
+
+    fn print_assumptions(&self) {
+        if self.assumptions.is_empty() {
+            println!("no assumptions");
+            return;
+        }
+
+        let assumptions = self
+            .assumptions
+            .iter()
+            .map(|(k, v)| format!("{k}={}", u8::from(*v)))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        println!("assuming {assumptions}");
+    }
+}
+
 fn print_help() {
     println!("enter a logical expression to evaluate. Example: !a v b");
+    println!(":dot <path> <expr> [@ a=1, b=0] to write the expression tree as a DOT graph");
+    println!(":svg <path> <expr> to write the optimized expression as a logic circuit");
+    println!(":codegen <rust|c|python|js|sql> <expr> to compile the expression into a function");
+    println!(":export <path> <expr> to stream the truth table to a CSV file");
+    println!(":eval <expr> @ a=1, b=0 to evaluate the expression for a single assignment");
+    println!(":table <expr>, <expr>, ... to compare several expressions in a single table");
+    println!(":table <expr> where <cond> to list only the rows that satisfy the condition");
+    println!(":table <expr> satisfying|falsifying to list only the rows with the given result");
+    println!(":table <expr>, <expr>, ... differing to list only the rows where they disagree");
+    println!(":steps <expr> to show every subexpression as entered, on the rows agreeing with the assumptions");
+    println!(":sat <expr> to find a satisfying assignment without building the truth table");
+    println!(":backbone <expr> to list the values forced in every model, given the assumptions");
+    println!(":cubes <expr> to list the models as cubes, with - for the values that do not matter");
+    println!(":count <expr> to count the satisfying assignments without enumerating them");
+    println!(":equiv <expr>, <expr> to check two expressions for equivalence");
+    println!(":mus <path> to find a minimal conflicting subset of the constraints in a file");
+    println!(":muses <path> to list every minimal conflicting subset and minimal correction set");
+    println!(":prove <expr> to prove the expression valid with a resolution refutation");
+    println!(":tableau [<path>.dot] <expr> to refute the negation with a tableau, or find a countermodel");
+    println!(":check <path> to check a natural deduction proof written in Fitch style");
+    println!("p -> q, p |= q to check whether the premises entail the conclusion");
+    println!(":assume a=1, b=0 to substitute the values into every following expression");
+    println!(":assume to list the current assumptions");
+    println!(":forget [a b] to drop the given assumptions, or all of them");
     println!("?, h or help for this list");
     println!("q or quit to exit");
 }
 
+fn print_table(table: Table) -> Result<(), String> {
+    let Table { header, rows } = table;
+
+    let table = rows
+        .iter()
+        .map(|v| {
+            v.iter().map(|v| {
+                v.then(|| "1".cell().bold(true).justify(Justify::Center))
+                    .unwrap_or_else(|| "0".cell().justify(Justify::Center))
+            })
+        })
+        .collect::<Vec<_>>()
+        .table()
+        .border(Border::builder().build())
+        .separator(Separator::builder().row(None).build())
+        .title(header)
+        .display()
+        .map_err(|e| e.to_string())?;
+
+    print!("{table}");
+
+    Ok(())
+}
+
+/// Parse an assignment such as `a=1, b=0`
+fn parse_assignment(assignment: &str) -> Result<Vec<(String, bool)>, String> {
+    assignment
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|s| !s.is_empty())
+        .map(|pair| {
+            let (name, value) = pair
+                .split_once('=')
+                .ok_or_else(|| format!("expected `name=value`, found `{pair}`"))?;
+
+            let value = match value.to_lowercase().as_str() {
+                "1" | "t" | "true" => true,
+                "0" | "f" | "false" => false,
+                _ => return Err(format!("invalid value `{value}` for `{name}`")),
+            };
+
+            Ok((name.to_string(), value))
+        })
+        .collect()
+}
+
+fn command_dot(args: &str) -> Result<(), String> {
+    let (path, expr) = args
+        .split_once(char::is_whitespace)
+        .ok_or("usage: :dot <path> <expr> [@ a=1, b=0]")?;
+
+    let (expr, assignment) = match expr.rsplit_once('@') {
+        Some((expr, assignment)) => (expr, Some(parse_assignment(assignment)?)),
+        None => (expr, None),
+    };
+
+    let instruction = Instruction::try_from(expr)?;
+    let graph = match assignment {
+        Some(assignment) => {
+            let values = assignment
+                .iter()
+                .map(|(k, v)| (k.as_str(), *v))
+                .collect::<Vec<_>>();
+
+            instruction.to_dot_with_values(&values)?
+        }
+        None => instruction.to_dot(),
+    };
+
+    fs::write(path, graph).map_err(|e| format!("failed to write {path}: {e}"))?;
+    println!("wrote {path}");
+
+    Ok(())
+}
+
+fn command_svg(args: &str) -> Result<(), String> {
+    let (path, expr) = args
+        .split_once(char::is_whitespace)
+        .ok_or("usage: :svg <path> <expr>")?;
+
+    let svg = Instruction::try_from(expr)?.optimize().to_svg();
+
+    fs::write(path, svg).map_err(|e| format!("failed to write {path}: {e}"))?;
+    println!("wrote {path}");
+
+    Ok(())
+}
+
+fn command_codegen(args: &str) -> Result<(), String> {
+    let (language, expr) = args
+        .split_once(char::is_whitespace)
+        .ok_or("usage: :codegen <rust|c|python|js|sql> <expr>")?;
+
+    let language = Language::try_from(language)?;
+    let code = Instruction::try_from(expr)?.compile(language, "f");
+
+    print!("{code}");
+
+    Ok(())
+}
+
+fn command_export(args: &str) -> Result<(), String> {
+    let (path, expr) = args
+        .split_once(char::is_whitespace)
+        .ok_or("usage: :export <path> <expr>")?;
+
+    let instruction = Instruction::try_from(expr)?.optimize();
+    let file = fs::File::create(path).map_err(|e| format!("failed to create {path}: {e}"))?;
+
+    instruction
+        .rows()?
+        .write_to(io::BufWriter::new(file))
+        .map_err(|e| format!("failed to write {path}: {e}"))?;
+
+    println!("wrote {path}");
+
+    Ok(())
+}
+
+fn command_eval(args: &str) -> Result<(), String> {
+    let (expr, assignment) = args
+        .rsplit_once('@')
+        .ok_or("usage: :eval <expr> @ a=1, b=0")?;
+
+    let assignment = parse_assignment(assignment)?;
+    let result = Instruction::try_from(expr)?.evaluate_with(&assignment)?;
+
+    println!("{}", u8::from(result));
+
+    Ok(())
+}
+
+fn command_assume(session: &mut Session, args: &str) -> Result<(), String> {
+    for (name, value) in parse_assignment(args)? {
+        match session.assumptions.iter_mut().find(|(k, _)| k == &name) {
+            Some((_, v)) => *v = value,
+            None => session.assumptions.push((name, value)),
+        }
+    }
+
+    session.print_assumptions();
+
+    Ok(())
+}
+
+fn command_forget(session: &mut Session, args: &str) -> Result<(), String> {
+    let names = args
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|s| !s.is_empty())
+        .collect::<Vec<_>>();
+
+    if names.is_empty() {
+        session.assumptions.clear();
+    } else {
+        session
+            .assumptions
+            .retain(|(k, _)| !names.contains(&k.as_str()));
+    }
+
+    session.print_assumptions();
+
+    Ok(())
+}
+
+fn command_steps(config: &Config, session: &Session, args: &str) -> Result<(), String> {
+    let instruction = Instruction::try_from(args)?;
+
+    // substituting the assumptions would rewrite the subexpressions, so they select the rows
+    let filter = session
+        .literals()
+        .reduce(|l, r| Instruction::And(Box::new(l), Box::new(r)))
+        .map_or(Filter::All, Filter::Where);
+
+    let options = TableOptions {
+        filter,
+        ..config.options.table()
+    };
+
+    print_table(instruction.steps(&options)?)
+}
+
+fn command_table(config: &Config, session: &Session, args: &str) -> Result<(), String> {
+    let (expr, filter) = if let Some((expr, condition)) = args.rsplit_once(" where ") {
+        let condition = session.prepare(Instruction::try_from(condition)?);
+        (expr, Filter::Where(condition))
+    } else if let Some(expr) = args.strip_suffix(" satisfying") {
+        (expr, Filter::Satisfying)
+    } else if let Some(expr) = args.strip_suffix(" falsifying") {
+        (expr, Filter::Falsifying)
+    } else if let Some(expr) = args.strip_suffix(" differing") {
+        (expr, Filter::Differing)
+    } else {
+        (args, Filter::All)
+    };
+
+    let expressions = Instruction::parse_list(expr)?;
+    let instructions = expressions
+        .iter()
+        .map(|(_, i)| session.prepare(i.clone()))
+        .collect::<Vec<_>>();
+
+    let options = TableOptions {
+        filter,
+        ..config.options.table()
+    };
+
+    let table = match instructions.as_slice() {
+        [instruction] => instruction.table(&options)?,
+        instructions => {
+            let mut table = Instruction::compare(instructions, &options)?;
+
+            // label the result columns with the expressions as entered, not as optimized
+            let first = table.header.len() - expressions.len() - 1;
+            for (header, (text, _)) in table.header[first..].iter_mut().zip(&expressions) {
+                *header = text.to_string();
+            }
+
+            table
+        }
+    };
+
+    print_table(table)
+}
+
+fn command_sat(session: &Session, args: &str) -> Result<(), String> {
+    let instruction = session.constrain(Instruction::try_from(args)?);
+
+    match instruction.satisfy() {
+        Some(model) => println!("satisfiable: {model}"),
+        None => println!("unsatisfiable"),
+    }
+
+    Ok(())
+}
+
+/// Split the arguments of a command into two expressions, either at a comma or at the only
+/// whitespace where both sides parse
+fn parse_pair(args: &str) -> Result<(Instruction, Instruction), String> {
+    if let Ok(expressions) = Instruction::parse_list(args) {
+        if let [(_, a), (_, b)] = expressions.as_slice() {
+            return Ok((a.clone(), b.clone()));
+        }
+    }
+
+    args.match_indices(char::is_whitespace)
+        .find_map(|(i, _)| {
+            let a = Instruction::try_from(&args[..i]).ok()?;
+            let b = Instruction::try_from(&args[i..]).ok()?;
+            Some((a, b))
+        })
+        .ok_or_else(|| "expected two expressions, such as `a ^ b, b ^ a`".to_string())
+}
+
+fn command_backbone(session: &Session, args: &str) -> Result<(), String> {
+    let instruction = Instruction::try_from(args)?;
+    let forced = instruction.implied(&session.assumptions)?;
+
+    if forced.is_empty() {
+        println!("no values are forced");
+        return Ok(());
+    }
+
+    let forced = forced
+        .iter()
+        .map(|(k, v)| format!("{k}={}", u8::from(*v)))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    println!("forced: {forced}");
+
+    Ok(())
+}
+
+fn command_cubes(session: &Session, args: &str) -> Result<(), String> {
+    let instruction = session.constrain(Instruction::try_from(args)?);
+    let mut cubes = 0;
+
+    for cube in instruction.cubes() {
+        println!("{cube}");
+        cubes += 1;
+    }
+
+    if cubes == 0 {
+        println!("unsatisfiable");
+    }
+
+    Ok(())
+}
+
+fn command_count(session: &Session, args: &str) -> Result<(), String> {
+    let instruction = session.constrain(Instruction::try_from(args)?);
+    let arguments = instruction.bytecode().arguments().len();
+
+    println!(
+        "{} of 2^{arguments} assignments",
+        instruction.count_models()
+    );
+
+    Ok(())
+}
+
+fn command_equiv(session: &Session, args: &str) -> Result<(), String> {
+    let (a, b) = parse_pair(args)?;
+    let (a, b) = (session.constrain(a), session.constrain(b));
+
+    match a.distinguish(&b) {
+        None => println!("equivalent"),
+        Some(model) => println!(
+            "not equivalent: {model} evaluates the first to {} and the second to {}",
+            u8::from(a.evaluate_with(&model)?),
+            u8::from(b.evaluate_with(&model)?)
+        ),
+    }
+
+    Ok(())
+}
+
+fn load_constraints(path: &str) -> Result<Constraints, String> {
+    let text = fs::read_to_string(path).map_err(|e| format!("failed to read {path}: {e}"))?;
+
+    Constraints::try_from(text.as_str())
+}
+
+fn command_mus(args: &str) -> Result<(), String> {
+    let constraints = load_constraints(args)?;
+
+    if let Some(model) = constraints.satisfy() {
+        println!("satisfiable: {model}");
+    } else if let Some(mus) = constraints.mus() {
+        println!("minimal unsatisfiable subset: {}", mus.join(", "));
+    }
+
+    Ok(())
+}
+
+fn command_muses(args: &str) -> Result<(), String> {
+    let constraints = load_constraints(args)?;
+
+    for subset in constraints.explore() {
+        match subset {
+            Subset::Mus(mus) => println!("unsatisfiable: {}", mus.join(", ")),
+            Subset::Mcs(mcs) if mcs.is_empty() => println!("satisfiable"),
+            Subset::Mcs(mcs) => println!("correction: {}", mcs.join(", ")),
+        }
+    }
+
+    Ok(())
+}
+
+/// Print the resolution refutation of a valid argument, or why it is not printed
+fn print_refutation(entailment: &Entailment) {
+    match entailment.prove() {
+        Ok(Some(refutation)) => println!("{refutation}"),
+        Ok(None) => (),
+        Err(e) => println!("no resolution proof printed: {e}"),
+    }
+}
+
+fn command_prove(session: &Session, args: &str) -> Result<(), String> {
+    let instruction = Instruction::try_from(args)?;
+    let entailment = Entailment::new(session.literals().collect(), instruction);
+
+    match entailment.check() {
+        Verdict::Valid => print_refutation(&entailment),
+        Verdict::Invalid { countermodel, .. } => {
+            println!("not valid: {countermodel} falsifies it");
+        }
+    }
+
+    Ok(())
+}
+
+fn command_tableau(session: &Session, args: &str) -> Result<(), String> {
+    let (path, expr) = match args.split_once(char::is_whitespace) {
+        Some((path, expr)) if path.ends_with(".dot") => (Some(path), expr),
+        _ => (None, args),
+    };
+
+    let instruction = Instruction::try_from(expr)?;
+    let mut formulas = session.literals().collect::<Vec<_>>();
+    formulas.push(Instruction::Not(Box::new(instruction)));
+
+    let tableau = Tableau::new(formulas)?;
+    println!("{tableau}");
+
+    match tableau.model() {
+        None => println!("closed: the expression is valid"),
+        Some(model) => println!("open: {model} falsifies the expression"),
+    }
+
+    if let Some(path) = path {
+        fs::write(path, tableau.to_dot()).map_err(|e| format!("failed to write {path}: {e}"))?;
+        println!("wrote {path}");
+    }
+
+    Ok(())
+}
+
+fn command_check(args: &str) -> Result<(), String> {
+    let text = fs::read_to_string(args).map_err(|e| format!("failed to read {args}: {e}"))?;
+    let proof = Proof::try_from(text.as_str())?;
+
+    if let Err(e) = proof.check() {
+        println!("invalid proof: {e}");
+        return Ok(());
+    }
+
+    if let Some(entailment) = proof.entailment() {
+        println!("valid proof of {entailment}");
+    }
+
+    Ok(())
+}
+
+/// Check an argument such as `p -> q, p |= q`, with the assumptions as additional premises
+fn check_entailment(session: &Session, mut entailment: Entailment) {
+    let verdict = entailment.check_assuming(session.literals().collect());
+    println!("{verdict}");
+
+    if verdict.is_valid() {
+        entailment.premises.extend(session.literals());
+        print_refutation(&entailment);
+    }
+}
+
+fn run_command(config: &Config, session: &mut Session, command: &str) -> Result<(), String> {
+    let command = command.trim();
+    let (name, args) = command
+        .split_once(char::is_whitespace)
+        .unwrap_or((command, ""));
+
+    match name.to_lowercase().as_str() {
+        "dot" => command_dot(args.trim()),
+        "svg" => command_svg(args.trim()),
+        "codegen" => command_codegen(args.trim()),
+        "export" => command_export(args.trim()),
+        "eval" => command_eval(args.trim()),
+        "table" => command_table(config, session, args.trim()),
+        "steps" => command_steps(config, session, args.trim()),
+        "sat" => command_sat(session, args.trim()),
+        "equiv" => command_equiv(session, args.trim()),
+        "count" => command_count(session, args.trim()),
+        "cubes" => command_cubes(session, args.trim()),
+        "backbone" => command_backbone(session, args.trim()),
+        "prove" => command_prove(session, args.trim()),
+        "tableau" => command_tableau(session, args.trim()),
+        "check" => command_check(args.trim()),
+        "mus" => command_mus(args.trim()),
+        "muses" => command_muses(args.trim()),
+        "assume" => command_assume(session, args.trim()),
+        "forget" => command_forget(session, args.trim()),
+        _ => Err(format!("unknown command `:{name}`; enter ? for help")),
+    }
+}
+
 fn main() {
     let config = Config::load().unwrap_or_else(|e| {
         eprintln!("failed to load config: {e}");
@@ -117,6 +710,8 @@ fn main() {
         }
     }
 
+    let mut session = Session::default();
+
     println!("welcome! enter ? for help");
 
     loop {
@@ -136,7 +731,19 @@ fn main() {
                     _ => (),
                 }
 
-                let instruction = match Instruction::try_from(line.as_str()) {
+                if let Some(command) = line.strip_prefix(':') {
+                    if let Err(e) = run_command(&config, &mut session, command) {
+                        eprintln!("error running command: {e}");
+                    }
+                    continue;
+                }
+
+                if let Ok(entailment) = Entailment::try_from(line.as_str()) {
+                    check_entailment(&session, entailment);
+                    continue;
+                }
+
+                let input = match Instruction::try_from(line.as_str()) {
                     Ok(i) => i,
                     Err(e) => {
                         eprintln!("error parsing line: {e}");
@@ -144,25 +751,27 @@ fn main() {
                     }
                 };
 
-                if instruction.eq_true() {
-                    if config.options.cheesy_mode {
+                let instruction = session.prepare(input.clone());
+                let classification = instruction.classify();
+                println!("{classification}");
+
+                // the optimizer folds a tautology to a constant, so prove the input as entered
+                if let Classification::Tautology(_) = classification {
+                    print_refutation(&Entailment::new(session.literals().collect(), input));
+                }
+
+                if instruction.eq_true() || instruction.eq_false() {
+                    if config.options.cheesy_mode && instruction.eq_true() {
                         println!("how very wet this water is...");
-                    } else {
-                        println!("true");
-                    }
-                    continue;
-                } else if instruction.eq_false() {
-                    if config.options.cheesy_mode {
+                    } else if config.options.cheesy_mode {
                         println!("well, that's just, like, your opinion, man...");
-                    } else {
-                        println!("false");
                     }
                     continue;
                 }
 
                 println!("evaluating {instruction}");
 
-                let Table { header, rows } = match instruction.evaluate() {
+                let table = match instruction.table(&config.options.table()) {
                     Ok(v) => v,
                     Err(e) => {
                         eprintln!("error evaluating instruction {instruction}: {e}");
@@ -170,29 +779,9 @@ fn main() {
                     }
                 };
 
-                let table = rows
-                    .iter()
-                    .map(|v| {
-                        v.iter().map(|v| {
-                            v.then(|| "1".cell().bold(true).justify(Justify::Center))
-                                .unwrap_or_else(|| "0".cell().justify(Justify::Center))
-                        })
-                    })
-                    .collect::<Vec<_>>()
-                    .table()
-                    .border(Border::builder().build())
-                    .separator(Separator::builder().row(None).build())
-                    .title(header);
-
-                let table = match table.display() {
-                    Ok(t) => t,
-                    Err(e) => {
-                        eprintln!("error displaying the evaluation: {e}");
-                        continue;
-                    }
-                };
-
-                print!("{table}");
+                if let Err(e) = print_table(table) {
+                    eprintln!("error displaying the evaluation: {e}");
+                }
             }
             Err(ReadlineError::Interrupted) => {
                 //eprintln!("CTRL-C");
@@ -0,0 +1,304 @@
+use super::*;
+use cnf::{Lit, Tseitin};
+use sat::Solver;
+
+#[cfg(test)]
+mod tests;
+
+/// A set of named constraints, satisfied by the assignments that satisfy all of them.
+///
+/// They are parsed from text with one `name: expression` per line. Blank lines and lines starting
+/// with `#` are ignored, and a constraint without a name is named after its line number.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Constraints {
+    constraints: Vec<(String, Instruction)>,
+}
+
+impl TryFrom<&str> for Constraints {
+    type Error = String;
+
+    fn try_from(text: &str) -> Result<Self, Self::Error> {
+        let lines = text
+            .lines()
+            .enumerate()
+            .map(|(i, line)| (i + 1, line.trim()))
+            .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'));
+
+        let mut constraints: Vec<(String, Instruction)> = vec![];
+        for (n, line) in lines {
+            let (name, expr) = match line.split_once(':') {
+                Some((name, expr)) if is_name(name.trim()) => (name.trim().to_string(), expr),
+                _ => (format!("line {n}"), line),
+            };
+
+            if constraints.iter().any(|(c, _)| *c == name) {
+                return Err(format!("line {n}: the name `{name}` is already used"));
+            }
+
+            let instruction = Instruction::try_from(expr).map_err(|e| format!("line {n}:\n{e}"))?;
+
+            constraints.push((name, instruction));
+        }
+
+        Ok(Self::new(constraints))
+    }
+}
+
+fn is_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '_' || c == '-' || c == ' ')
+}
+
+/// A subset of the constraints found while exploring them.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Subset {
+    /// A minimal unsatisfiable subset: the constraints conflict, but every proper subset of them
+    /// is satisfiable.
+    Mus(Vec<String>),
+    /// A minimal correction set: dropping the constraints makes the rest satisfiable, but dropping
+    /// any proper subset of them does not.
+    Mcs(Vec<String>),
+}
+
+/// Constraints encoded into a solver, each enabled by assuming its selector.
+struct Encoding {
+    solver: Solver,
+    selectors: Vec<Lit>,
+    roots: Vec<Lit>,
+    arguments: Vec<String>,
+}
+
+impl Encoding {
+    fn new(constraints: &Constraints) -> Self {
+        let mut tseitin = Tseitin::new(vec![]);
+        let mut selectors = vec![];
+        let mut roots = vec![];
+
+        for (_, instruction) in &constraints.constraints {
+            let root = tseitin.encode(instruction);
+            let selector = tseitin.fresh();
+
+            tseitin.clause(vec![!selector, root]);
+            selectors.push(selector);
+            roots.push(root);
+        }
+
+        let cnf = tseitin.finish();
+
+        Self {
+            solver: Solver::with_cnf(&cnf),
+            selectors,
+            roots,
+            arguments: cnf.arguments,
+        }
+    }
+
+    fn solve(&mut self, subset: &[usize]) -> bool {
+        let assumptions = subset
+            .iter()
+            .map(|i| self.selectors[*i])
+            .collect::<Vec<_>>();
+
+        self.solver.solve_with(&assumptions)
+    }
+
+    /// The constraints of the subset whose selectors were in the failed assumptions.
+    fn core(&self, subset: &[usize]) -> Vec<usize> {
+        let failed = self.solver.failed();
+
+        subset
+            .iter()
+            .copied()
+            .filter(|i| failed.contains(&self.selectors[*i]))
+            .collect()
+    }
+
+    /// Shrink an unsatisfiable subset to a minimal one, by deleting every constraint that is not
+    /// needed, and narrowing to the failed assumptions after every unsatisfiable check.
+    fn shrink(&mut self, subset: &[usize]) -> Vec<usize> {
+        self.solve(subset);
+        let mut mus = self.core(subset);
+
+        let mut i = 0;
+        while i < mus.len() {
+            let without = mus
+                .iter()
+                .copied()
+                .filter(|c| *c != mus[i])
+                .collect::<Vec<_>>();
+
+            if self.solve(&without) {
+                i += 1;
+            } else {
+                let core = self.core(&without);
+                i = core.iter().filter(|c| mus[..i].contains(c)).count();
+                mus = core;
+            }
+        }
+
+        mus
+    }
+
+    /// Grow a satisfiable subset to a maximal one, adding at once every constraint satisfied by
+    /// each new model.
+    ///
+    /// The subset must have been the last one solved.
+    fn grow(&mut self, subset: &[usize]) -> Vec<usize> {
+        let mut mss = subset.to_vec();
+        self.absorb(&mut mss);
+
+        for i in 0..self.selectors.len() {
+            if mss.contains(&i) {
+                continue;
+            }
+
+            mss.push(i);
+            if self.solve(&mss) {
+                self.absorb(&mut mss);
+            } else {
+                mss.pop();
+            }
+        }
+
+        mss.sort_unstable();
+        mss
+    }
+
+    /// Add the constraints that are satisfied by the last model.
+    fn absorb(&self, subset: &mut Vec<usize>) {
+        for (i, root) in self.roots.iter().enumerate() {
+            let satisfied = self.solver.model(root.variable()) == Some(root.is_positive());
+
+            if satisfied && !subset.contains(&i) {
+                subset.push(i);
+            }
+        }
+    }
+
+    fn model(&self) -> Model {
+        let mut values = self
+            .arguments
+            .iter()
+            .enumerate()
+            .map(|(v, name)| (name.clone(), self.solver.model(v).unwrap_or(false)))
+            .collect::<Vec<_>>();
+
+        values.sort();
+
+        Model::new(values)
+    }
+}
+
+impl Constraints {
+    pub fn new(constraints: Vec<(String, Instruction)>) -> Self {
+        Self { constraints }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Instruction)> {
+        self.constraints.iter().map(|(k, v)| (k.as_str(), v))
+    }
+
+    pub fn len(&self) -> usize {
+        self.constraints.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.constraints.is_empty()
+    }
+
+    fn names(&self, subset: &[usize]) -> Vec<String> {
+        subset
+            .iter()
+            .map(|i| self.constraints[*i].0.clone())
+            .collect()
+    }
+
+    /// An assignment that satisfies every constraint, if any.
+    pub fn satisfy(&self) -> Option<Model> {
+        let mut encoding = Encoding::new(self);
+        let all = (0..self.len()).collect::<Vec<_>>();
+
+        encoding.solve(&all).then(|| encoding.model())
+    }
+
+    /// The names of a minimal unsatisfiable subset of the constraints, in their order, or `None`
+    /// if they are satisfiable.
+    pub fn mus(&self) -> Option<Vec<String>> {
+        let mut encoding = Encoding::new(self);
+        let all = (0..self.len()).collect::<Vec<_>>();
+
+        if encoding.solve(&all) {
+            return None;
+        }
+
+        let mut mus = encoding.shrink(&all);
+        mus.sort_unstable();
+
+        Some(self.names(&mus))
+    }
+
+    /// Lazily enumerate every minimal unsatisfiable subset and every minimal correction set.
+    pub fn explore(&self) -> Explorer<'_> {
+        Explorer::new(self)
+    }
+}
+
+/// Enumeration of the minimal unsatisfiable subsets and minimal correction sets of constraints.
+///
+/// A map solver over one variable per constraint keeps the subsets that were not explored yet.
+/// Each seed it proposes is either unsatisfiable, and shrunk to a minimal unsatisfiable subset
+/// whose supersets are then blocked, or satisfiable, and grown to a maximal satisfiable subset
+/// whose complement is a minimal correction set, and whose subsets are then blocked.
+pub struct Explorer<'a> {
+    constraints: &'a Constraints,
+    encoding: Encoding,
+    map: Solver,
+}
+
+impl<'a> Explorer<'a> {
+    fn new(constraints: &'a Constraints) -> Self {
+        let mut map = Solver::new();
+        map.reserve(constraints.len());
+
+        Self {
+            constraints,
+            encoding: Encoding::new(constraints),
+            map,
+        }
+    }
+}
+
+impl<'a> Iterator for Explorer<'a> {
+    type Item = Subset;
+
+    fn next(&mut self) -> Option<Subset> {
+        if !self.map.solve() {
+            return None;
+        }
+
+        let n = self.constraints.len();
+        let seed = (0..n)
+            .filter(|i| self.map.model(*i) == Some(true))
+            .collect::<Vec<_>>();
+
+        if self.encoding.solve(&seed) {
+            let mss = self.encoding.grow(&seed);
+            let mcs = (0..n).filter(|i| !mss.contains(i)).collect::<Vec<_>>();
+
+            let block = mcs.iter().map(|i| Lit::new(*i, true)).collect::<Vec<_>>();
+            self.map.add_clause(&block);
+
+            Some(Subset::Mcs(self.constraints.names(&mcs)))
+        } else {
+            let mut mus = self.encoding.shrink(&seed);
+            mus.sort_unstable();
+
+            let block = mus.iter().map(|i| Lit::new(*i, false)).collect::<Vec<_>>();
+            self.map.add_clause(&block);
+
+            Some(Subset::Mus(self.constraints.names(&mus)))
+        }
+    }
+}
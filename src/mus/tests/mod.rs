@@ -0,0 +1,100 @@
+use super::*;
+
+const RULES: &str = "
+# product configuration rules
+premium: p
+basic: b
+exclusive: !(p ^ b)
+needs-license: p -> l
+no-license: !l
+turbo: t
+turbo-needs-premium: t -> p
+";
+
+fn names(names: &[&str]) -> Vec<String> {
+    names.iter().map(|n| n.to_string()).collect()
+}
+
+fn is_satisfiable(constraints: &Constraints, subset: &[String]) -> bool {
+    let subset = constraints
+        .iter()
+        .filter(|(name, _)| subset.iter().any(|s| s == name))
+        .map(|(name, i)| (name.to_string(), i.clone()))
+        .collect();
+
+    Constraints::new(subset).satisfy().is_some()
+}
+
+#[test]
+fn parse_constraints() {
+    let constraints = Constraints::try_from(RULES).unwrap();
+
+    assert_eq!(7, constraints.len());
+    assert_eq!(
+        Some(("needs-license", &Instruction::try_from("p -> l").unwrap())),
+        constraints.iter().nth(3)
+    );
+
+    let constraints = Constraints::try_from("a\n\nrule: !a").unwrap();
+    let names = constraints.iter().map(|(n, _)| n).collect::<Vec<_>>();
+    assert_eq!(vec!["line 1", "rule"], names);
+
+    assert!(Constraints::try_from("a: b v").is_err());
+    assert_eq!(
+        Err("line 3: the name `rule` is already used".to_string()),
+        Constraints::try_from("rule: a\n\nrule: !a").map(|_| ())
+    );
+}
+
+#[test]
+fn minimal_unsatisfiable_subset() {
+    let constraints = Constraints::try_from(RULES).unwrap();
+    let mus = constraints.mus().unwrap();
+
+    assert!(!is_satisfiable(&constraints, &mus));
+    for i in 0..mus.len() {
+        let mut subset = mus.clone();
+        subset.remove(i);
+        assert!(is_satisfiable(&constraints, &subset));
+    }
+
+    let satisfiable = Constraints::try_from("a: x\nb: x -> y").unwrap();
+    assert_eq!(None, satisfiable.mus());
+    assert!(satisfiable.satisfy().is_some());
+}
+
+#[test]
+fn explore_subsets() {
+    let constraints = Constraints::try_from(RULES).unwrap();
+
+    let mut muses = vec![];
+    let mut mcses = vec![];
+    for subset in constraints.explore() {
+        match subset {
+            Subset::Mus(mus) => muses.push(mus),
+            Subset::Mcs(mcs) => mcses.push(mcs),
+        }
+    }
+
+    muses.sort();
+    assert_eq!(
+        vec![
+            names(&["basic", "exclusive", "turbo", "turbo-needs-premium"]),
+            names(&[
+                "needs-license",
+                "no-license",
+                "turbo",
+                "turbo-needs-premium"
+            ]),
+            names(&["premium", "basic", "exclusive"]),
+            names(&["premium", "needs-license", "no-license"]),
+        ],
+        muses
+    );
+
+    // every correction set hits every unsatisfiable subset
+    assert!(!mcses.is_empty());
+    for mcs in &mcses {
+        assert!(muses.iter().all(|mus| mus.iter().any(|c| mcs.contains(c))));
+    }
+}
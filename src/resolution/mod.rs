@@ -0,0 +1,536 @@
+use super::*;
+use cnf::Lit;
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
+
+#[cfg(test)]
+mod tests;
+
+/// Largest number of clauses of the clausal form of an input.
+const MAX_CLAUSES: usize = 4096;
+
+/// Largest number of steps of a derivation.
+const MAX_STEPS: usize = 10_000;
+
+/// Disjunction of arguments and negated arguments, sorted by argument.
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Clause {
+    literals: Vec<(String, bool)>,
+}
+
+impl Clause {
+    pub fn new(mut literals: Vec<(String, bool)>) -> Self {
+        literals.sort();
+        literals.dedup();
+
+        Self { literals }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, bool)> {
+        self.literals.iter().map(|(k, v)| (k.as_str(), *v))
+    }
+
+    pub fn len(&self) -> usize {
+        self.literals.len()
+    }
+
+    /// Whether the clause is the empty clause, which no assignment satisfies.
+    pub fn is_empty(&self) -> bool {
+        self.literals.is_empty()
+    }
+
+    pub fn contains(&self, argument: &str, positive: bool) -> bool {
+        self.iter().any(|l| l == (argument, positive))
+    }
+
+    /// Whether the clause contains an argument and its negation, so every assignment satisfies it.
+    pub fn is_tautology(&self) -> bool {
+        self.literals.windows(2).any(|w| w[0].0 == w[1].0)
+    }
+
+    /// The resolvent of a clause that contains the pivot and a clause that contains its negation.
+    pub fn resolve(&self, other: &Clause, pivot: &str) -> Option<Clause> {
+        if !self.contains(pivot, true) || !other.contains(pivot, false) {
+            return None;
+        }
+
+        let literals = self
+            .literals
+            .iter()
+            .chain(other.literals.iter())
+            .filter(|(k, _)| k != pivot)
+            .cloned()
+            .collect();
+
+        Some(Self::new(literals))
+    }
+
+    fn union(&self, other: &Clause) -> Clause {
+        Self::new(
+            self.literals
+                .iter()
+                .chain(other.literals.iter())
+                .cloned()
+                .collect(),
+        )
+    }
+}
+
+impl fmt::Display for Clause {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let literals = self
+            .iter()
+            .map(|(k, v)| if v { k.to_string() } else { format!("!{k}") })
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        write!(f, "{{{literals}}}")
+    }
+}
+
+/// The clauses of an instruction, by distributing its negation normal form, without auxiliary
+/// variables so every clause reads in terms of the arguments.
+///
+/// Fails if the clausal form is too large.
+pub fn clausal_form(instruction: &Instruction) -> Result<Vec<Clause>, String> {
+    let mut clauses = clauses(instruction, true)?;
+
+    clauses.sort();
+    clauses.dedup();
+
+    Ok(clauses)
+}
+
+/// The clauses of the instruction, or of its negation.
+fn clauses(instruction: &Instruction, positive: bool) -> Result<Vec<Clause>, String> {
+    use Instruction::*;
+
+    // a tautology has no clauses, and a contradiction has the empty clause
+    let constant = |value: bool| match value {
+        true => vec![],
+        false => vec![Clause::default()],
+    };
+
+    let clauses = match instruction {
+        True => constant(positive),
+        False => constant(!positive),
+        Argument(a) => vec![Clause::new(vec![(a.clone(), positive)])],
+        Not(x) => clauses(x, !positive)?,
+
+        And(l, r) if positive => union(clauses(l, true)?, clauses(r, true)?),
+        And(l, r) => product(clauses(l, false)?, clauses(r, false)?)?,
+        Or(l, r) if positive => product(clauses(l, true)?, clauses(r, true)?)?,
+        Or(l, r) => union(clauses(l, false)?, clauses(r, false)?),
+        Conditional(l, r) if positive => product(clauses(l, false)?, clauses(r, true)?)?,
+        Conditional(l, r) => union(clauses(l, true)?, clauses(r, false)?),
+
+        Biconditional(l, r) | Equals(l, r) | Xor(l, r) => {
+            let equal = !matches!(instruction, Xor(_, _)) == positive;
+
+            if equal {
+                union(
+                    product(clauses(l, false)?, clauses(r, true)?)?,
+                    product(clauses(l, true)?, clauses(r, false)?)?,
+                )
+            } else {
+                union(
+                    product(clauses(l, true)?, clauses(r, true)?)?,
+                    product(clauses(l, false)?, clauses(r, false)?)?,
+                )
+            }
+        }
+    };
+
+    if clauses.len() > MAX_CLAUSES {
+        return Err(format!(
+            "the clausal form has more than {MAX_CLAUSES} clauses"
+        ));
+    }
+
+    Ok(clauses)
+}
+
+fn union(mut l: Vec<Clause>, r: Vec<Clause>) -> Vec<Clause> {
+    l.extend(r);
+    l
+}
+
+/// The clauses of the disjunction of two conjunctions of clauses.
+fn product(l: Vec<Clause>, r: Vec<Clause>) -> Result<Vec<Clause>, String> {
+    if l.len().saturating_mul(r.len()) > MAX_CLAUSES {
+        return Err(format!(
+            "the clausal form has more than {MAX_CLAUSES} clauses"
+        ));
+    }
+
+    let clauses = l
+        .iter()
+        .flat_map(|a| r.iter().map(move |b| a.union(b)))
+        .filter(|c| !c.is_tautology())
+        .collect::<BTreeSet<_>>();
+
+    Ok(clauses.into_iter().collect())
+}
+
+/// The reason a clause of a derivation holds.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Justification {
+    /// A clause of the clausal form of the input with the given index.
+    Input(usize),
+    /// The resolvent of two previous steps, by index, the first containing the pivot and the
+    /// second its negation.
+    Resolvent {
+        left: usize,
+        right: usize,
+        pivot: String,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Step {
+    pub clause: Clause,
+    pub justification: Justification,
+}
+
+/// Derivation of the empty clause from the clauses of named inputs, which proves that the inputs
+/// cannot hold together.
+///
+/// Every step is either a clause of an input or the resolvent of two previous steps, and the last
+/// step is the empty clause. Only the steps the empty clause depends on are kept.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Refutation {
+    inputs: Vec<(String, Instruction)>,
+    steps: Vec<Step>,
+}
+
+impl Refutation {
+    /// Search for a refutation of the conjunction of the inputs.
+    ///
+    /// The search is a DPLL procedure on the clauses: every closed branch of the search tree is
+    /// turned into a resolution step, so the refutation is found if and only if the inputs are
+    /// unsatisfiable. Returns `None` if they are satisfiable.
+    ///
+    /// Fails if the clausal form of an input, or the derivation, is too large to print.
+    pub fn prove(inputs: Vec<(String, Instruction)>) -> Result<Option<Self>, String> {
+        let mut clauses = vec![];
+        for (i, (_, instruction)) in inputs.iter().enumerate() {
+            clauses.extend(clausal_form(instruction)?.into_iter().map(|c| (c, i)));
+        }
+
+        let arguments = clauses
+            .iter()
+            .flat_map(|(c, _)| c.iter().map(|(k, _)| k.to_string()))
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect::<Vec<_>>();
+
+        let mut prover = Prover::new(&arguments, clauses);
+        let mut values = vec![None; arguments.len()];
+
+        let Some(root) = prover.refute(&mut values)? else {
+            return Ok(None);
+        };
+
+        let steps = prover.extract(root, &arguments);
+
+        Ok(Some(Self { inputs, steps }))
+    }
+
+    pub fn inputs(&self) -> impl Iterator<Item = (&str, &Instruction)> {
+        self.inputs.iter().map(|(k, v)| (k.as_str(), v))
+    }
+
+    pub fn steps(&self) -> &[Step] {
+        &self.steps
+    }
+
+    pub fn len(&self) -> usize {
+        self.steps.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    /// Check every step of the derivation, independently of the prover that produced it.
+    ///
+    /// Fails with the number of the first invalid step and the reason.
+    pub fn check(&self) -> Result<(), String> {
+        let inputs = self
+            .inputs
+            .iter()
+            .map(|(_, instruction)| clausal_form(instruction))
+            .collect::<Result<Vec<_>, String>>()?;
+
+        for (i, step) in self.steps.iter().enumerate() {
+            let n = i + 1;
+
+            match &step.justification {
+                Justification::Input(input) => {
+                    let clauses = inputs
+                        .get(*input)
+                        .ok_or_else(|| format!("step {n}: there is no input {}", input + 1))?;
+
+                    if !clauses.contains(&step.clause) {
+                        return Err(format!(
+                            "step {n}: {} is not a clause of {}",
+                            step.clause, self.inputs[*input].0
+                        ));
+                    }
+                }
+
+                Justification::Resolvent { left, right, pivot } => {
+                    if *left >= i || *right >= i {
+                        return Err(format!("step {n}: only previous steps can be resolved"));
+                    }
+
+                    let resolvent = self.steps[*left]
+                        .clause
+                        .resolve(&self.steps[*right].clause, pivot)
+                        .ok_or_else(|| {
+                            format!(
+                                "step {n}: steps {} and {} cannot be resolved on {pivot}",
+                                left + 1,
+                                right + 1
+                            )
+                        })?;
+
+                    if resolvent != step.clause {
+                        return Err(format!(
+                            "step {n}: the resolvent of steps {} and {} on {pivot} is {resolvent}",
+                            left + 1,
+                            right + 1
+                        ));
+                    }
+                }
+            }
+        }
+
+        match self.steps.last() {
+            Some(step) if step.clause.is_empty() => Ok(()),
+            _ => Err("the derivation does not end with the empty clause".to_string()),
+        }
+    }
+}
+
+impl fmt::Display for Refutation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let numbers = self.steps.len().to_string().len();
+        let clauses = self
+            .steps
+            .iter()
+            .map(|s| s.clause.to_string())
+            .collect::<Vec<_>>();
+        let width = clauses.iter().map(|c| c.len()).max().unwrap_or(0);
+
+        for (i, (step, clause)) in self.steps.iter().zip(clauses).enumerate() {
+            let justification = match &step.justification {
+                Justification::Input(input) => self.inputs[*input].0.clone(),
+                Justification::Resolvent { left, right, pivot } => {
+                    format!("resolve {}, {} on {pivot}", left + 1, right + 1)
+                }
+            };
+
+            if i > 0 {
+                writeln!(f)?;
+            }
+
+            write!(f, "{:>numbers$}. {clause:<width$}  {justification}", i + 1)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The reason of a step of the prover, with the pivot as a variable.
+enum Reason {
+    Input(usize),
+    Resolvent {
+        left: usize,
+        right: usize,
+        pivot: usize,
+    },
+}
+
+/// DPLL search that records the derivation of a clause falsified by every closed branch.
+struct Prover {
+    clauses: Vec<(Vec<Lit>, usize)>,
+    steps: Vec<(Vec<Lit>, Reason)>,
+    derived: HashMap<Vec<Lit>, usize>,
+}
+
+impl Prover {
+    fn new(arguments: &[String], clauses: Vec<(Clause, usize)>) -> Self {
+        let index = |argument: &str| {
+            arguments
+                .binary_search_by(|a| a.as_str().cmp(argument))
+                .expect("the arguments contain every literal")
+        };
+
+        let clauses = clauses
+            .into_iter()
+            .map(|(clause, input)| {
+                let lits = clause.iter().map(|(k, v)| Lit::new(index(k), v)).collect();
+                (lits, input)
+            })
+            .collect();
+
+        Self {
+            clauses,
+            steps: vec![],
+            derived: HashMap::new(),
+        }
+    }
+
+    fn step(&mut self, clause: Vec<Lit>, reason: Reason) -> Result<usize, String> {
+        if let Some(step) = self.derived.get(&clause) {
+            return Ok(*step);
+        }
+
+        if self.steps.len() == MAX_STEPS {
+            return Err(format!("the refutation has more than {MAX_STEPS} steps"));
+        }
+
+        self.derived.insert(clause.clone(), self.steps.len());
+        self.steps.push((clause, reason));
+
+        Ok(self.steps.len() - 1)
+    }
+
+    /// The step of a clause falsified by the partial assignment, or `None` if the assignment
+    /// extends to a model of the clauses.
+    fn refute(&mut self, values: &mut [Option<bool>]) -> Result<Option<usize>, String> {
+        let value =
+            |values: &[Option<bool>], l: &Lit| values[l.variable()].map(|v| v == l.is_positive());
+
+        let mut unit = None;
+        let mut occurrences = vec![0usize; values.len()];
+
+        for (clause, input) in &self.clauses {
+            if clause.iter().any(|l| value(values, l) == Some(true)) {
+                continue;
+            }
+
+            let open = clause
+                .iter()
+                .filter(|l| value(values, l).is_none())
+                .collect::<Vec<_>>();
+
+            match open.as_slice() {
+                [] => {
+                    let (clause, input) = (clause.clone(), *input);
+                    return self.step(clause, Reason::Input(input)).map(Some);
+                }
+                [l] if unit.is_none() => unit = Some(**l),
+                _ => (),
+            }
+
+            for l in open {
+                occurrences[l.variable()] += 1;
+            }
+        }
+
+        // a unit literal is branched on with its falsifying side first, which closes at once
+        let (variable, first) = match unit {
+            Some(l) => (l.variable(), !l.is_positive()),
+            None => match (0..values.len()).max_by_key(|v| (occurrences[*v], usize::MAX - v)) {
+                Some(v) if occurrences[v] > 0 => (v, false),
+                _ => return Ok(None),
+            },
+        };
+
+        let mut sides = [0; 2];
+        for (side, value) in [first, !first].into_iter().enumerate() {
+            values[variable] = Some(value);
+            let step = self.refute(values);
+            values[variable] = None;
+
+            let Some(step) = step? else {
+                return Ok(None);
+            };
+
+            // a clause that does not mention the variable is falsified without it
+            if !self.steps[step].0.contains(&Lit::new(variable, !value)) {
+                return Ok(Some(step));
+            }
+
+            sides[side] = step;
+        }
+
+        let (left, right) = match first {
+            false => (sides[0], sides[1]),
+            true => (sides[1], sides[0]),
+        };
+
+        let resolvent = self.steps[left]
+            .0
+            .iter()
+            .chain(self.steps[right].0.iter())
+            .filter(|l| l.variable() != variable)
+            .copied()
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+
+        let reason = Reason::Resolvent {
+            left,
+            right,
+            pivot: variable,
+        };
+
+        self.step(resolvent, reason).map(Some)
+    }
+
+    /// The steps the root depends on, named after the arguments and renumbered with the input
+    /// clauses first, in the order of the inputs, followed by the resolvents.
+    fn extract(&self, root: usize, arguments: &[String]) -> Vec<Step> {
+        let mut used = BTreeSet::new();
+        let mut pending = vec![root];
+
+        while let Some(step) = pending.pop() {
+            if used.insert(step) {
+                if let Reason::Resolvent { left, right, .. } = &self.steps[step].1 {
+                    pending.extend([*left, *right]);
+                }
+            }
+        }
+
+        let mut used = used.into_iter().collect::<Vec<_>>();
+        used.sort_by_key(|step| match self.steps[*step].1 {
+            Reason::Input(input) => (0, input, *step),
+            Reason::Resolvent { .. } => (1, 0, *step),
+        });
+
+        let numbers = used
+            .iter()
+            .enumerate()
+            .map(|(n, step)| (*step, n))
+            .collect::<HashMap<_, _>>();
+
+        used.into_iter()
+            .map(|step| {
+                let (lits, reason) = &self.steps[step];
+
+                let clause = Clause::new(
+                    lits.iter()
+                        .map(|l| (arguments[l.variable()].clone(), l.is_positive()))
+                        .collect(),
+                );
+
+                let justification = match reason {
+                    Reason::Input(input) => Justification::Input(*input),
+                    Reason::Resolvent { left, right, pivot } => Justification::Resolvent {
+                        left: numbers[left],
+                        right: numbers[right],
+                        pivot: arguments[*pivot].clone(),
+                    },
+                };
+
+                Step {
+                    clause,
+                    justification,
+                }
+            })
+            .collect()
+    }
+}
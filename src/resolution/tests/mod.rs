@@ -0,0 +1,169 @@
+use super::*;
+
+struct Random(u64);
+
+impl Random {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn below(&mut self, n: u64) -> u64 {
+        self.next() % n
+    }
+}
+
+fn random_formula(random: &mut Random, depth: usize) -> String {
+    if depth == 0 || random.below(4) == 0 {
+        let argument = ["a", "b", "c", "d"][random.below(4) as usize];
+        return match random.below(2) {
+            0 => argument.to_string(),
+            _ => format!("!{argument}"),
+        };
+    }
+
+    let operator = ["^", "v", "->", "<->", "+"][random.below(5) as usize];
+    let l = random_formula(random, depth - 1);
+    let r = random_formula(random, depth - 1);
+
+    format!("({l} {operator} {r})")
+}
+
+fn clause(text: &str) -> Clause {
+    let literals = text
+        .split_whitespace()
+        .map(|l| match l.strip_prefix('!') {
+            Some(l) => (l.to_string(), false),
+            None => (l.to_string(), true),
+        })
+        .collect();
+
+    Clause::new(literals)
+}
+
+#[test]
+fn clausal_form_of_connectives() {
+    let cases = [
+        ("a -> b", vec!["!a b"]),
+        ("!(a -> b)", vec!["a", "!b"]),
+        ("a <-> b", vec!["!a b", "!b a"]),
+        ("a + b", vec!["!a !b", "a b"]),
+        ("!(a ^ b) v c", vec!["!a !b c"]),
+        ("a v !a", vec![]),
+    ];
+
+    for (instruction, expected) in cases {
+        let instruction = Instruction::try_from(instruction).unwrap();
+        let expected = expected.into_iter().map(clause).collect::<Vec<_>>();
+
+        assert_eq!(
+            expected,
+            clausal_form(&instruction).unwrap(),
+            "{instruction}"
+        );
+    }
+
+    let parity = (0..16)
+        .map(|i| format!("x{i}"))
+        .collect::<Vec<_>>()
+        .join(" + ");
+    let parity = Instruction::try_from(parity.as_str()).unwrap();
+    assert!(clausal_form(&parity).is_err());
+}
+
+#[test]
+fn resolve_clauses() {
+    assert_eq!(
+        Some(clause("b c")),
+        clause("a b").resolve(&clause("!a c"), "a")
+    );
+    assert_eq!(Some(clause("")), clause("a").resolve(&clause("!a"), "a"));
+    assert_eq!(None, clause("!a b").resolve(&clause("a c"), "a"));
+}
+
+#[test]
+fn prove_tautologies() {
+    let tautologies = [
+        "a v !a",
+        "((a -> b) -> a) -> a",
+        "(a -> b) <-> (!b -> !a)",
+        "!(a ^ b) <-> (!a v !b)",
+        "(a -> (b -> c)) -> ((a -> b) -> (a -> c))",
+    ];
+
+    for tautology in tautologies {
+        let instruction = Instruction::try_from(tautology).unwrap();
+        let refutation = instruction.prove().unwrap().unwrap();
+
+        assert_eq!(Ok(()), refutation.check(), "{tautology}");
+        assert!(refutation.steps().last().unwrap().clause.is_empty());
+    }
+
+    let contingent = Instruction::try_from("a -> b").unwrap();
+    assert_eq!(None, contingent.prove().unwrap());
+}
+
+#[test]
+fn prove_entailment() {
+    let entailment = Entailment::try_from("p -> q, q -> r, p |= r").unwrap();
+    let refutation = entailment.prove().unwrap().unwrap();
+
+    assert_eq!(Ok(()), refutation.check());
+    assert_eq!(
+        "1. {!p, q}  premise 1
+2. {!q, r}  premise 2
+3. {p}      premise 3
+4. {!r}     negated conclusion
+5. {!q}     resolve 2, 4 on r
+6. {!p}     resolve 1, 5 on q
+7. {}       resolve 3, 6 on p",
+        refutation.to_string()
+    );
+
+    let fallacy = Entailment::try_from("p -> q, q |= p").unwrap();
+    assert_eq!(None, fallacy.prove().unwrap());
+}
+
+#[test]
+fn check_rejects_invalid_steps() {
+    let entailment = Entailment::try_from("p -> q, p |= q").unwrap();
+    let refutation = entailment.prove().unwrap().unwrap();
+
+    let mut forged = refutation.clone();
+    forged.steps[0].clause = clause("q");
+    assert!(forged.check().unwrap_err().starts_with("step 1:"));
+
+    let mut forged = refutation.clone();
+    let last = forged.steps.len() - 1;
+    if let Justification::Resolvent { pivot, .. } = &mut forged.steps[last].justification {
+        *pivot = "z".to_string();
+    }
+    assert!(forged
+        .check()
+        .unwrap_err()
+        .starts_with(&format!("step {}:", last + 1)));
+
+    let mut forged = refutation;
+    forged.steps.pop();
+    assert!(forged.check().is_err());
+}
+
+#[test]
+fn prove_agrees_with_classify() {
+    let mut random = Random(0x2545_f491_4f6c_dd1d);
+
+    for _ in 0..300 {
+        let formula = random_formula(&mut random, 4);
+        let instruction = Instruction::try_from(formula.as_str()).unwrap();
+
+        match instruction.prove().unwrap() {
+            Some(refutation) => {
+                assert!(instruction.classify().is_tautology(), "{formula}");
+                assert_eq!(Ok(()), refutation.check(), "{formula}");
+            }
+            None => assert!(!instruction.classify().is_tautology(), "{formula}"),
+        }
+    }
+}
@@ -0,0 +1,692 @@
+use super::*;
+use cnf::{Cnf, Lit};
+use std::fmt;
+
+#[cfg(test)]
+mod tests;
+
+/// Number of conflicts of the first restart, scaled by the Luby sequence.
+const RESTART_BASE: u64 = 100;
+
+/// Decay factor of the variable activities.
+const ACTIVITY_DECAY: f64 = 0.95;
+
+/// Assignment of the arguments of an instruction that satisfies it.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Model {
+    values: Vec<(String, bool)>,
+}
+
+impl Model {
+    pub fn new(values: Vec<(String, bool)>) -> Self {
+        Self { values }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, bool)> {
+        self.values.iter().map(|(k, v)| (k.as_str(), *v))
+    }
+}
+
+impl Assignment for Model {
+    fn value(&self, argument: &str) -> Option<bool> {
+        self.values.value(argument)
+    }
+}
+
+impl fmt::Display for Model {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let values = self
+            .iter()
+            .map(|(k, v)| format!("{k}={}", u8::from(v)))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        write!(f, "{values}")
+    }
+}
+
+/// Binary max-heap of variables ordered by activity, with the position of every variable so its
+/// priority can be increased in place.
+#[derive(Debug, Default)]
+struct Heap {
+    heap: Vec<usize>,
+    positions: Vec<Option<usize>>,
+}
+
+impl Heap {
+    fn contains(&self, v: usize) -> bool {
+        self.positions[v].is_some()
+    }
+
+    fn push(&mut self, v: usize, activity: &[f64]) {
+        if v >= self.positions.len() {
+            self.positions.resize(v + 1, None);
+        }
+
+        if self.contains(v) {
+            return;
+        }
+
+        self.positions[v] = Some(self.heap.len());
+        self.heap.push(v);
+        self.up(self.heap.len() - 1, activity);
+    }
+
+    fn pop(&mut self, activity: &[f64]) -> Option<usize> {
+        let top = *self.heap.first()?;
+        let last = self.heap.pop().expect("the heap is not empty");
+
+        self.positions[top] = None;
+        if !self.heap.is_empty() {
+            self.heap[0] = last;
+            self.positions[last] = Some(0);
+            self.down(0, activity);
+        }
+
+        Some(top)
+    }
+
+    /// Restore the order after the activity of the variable increased.
+    fn increase(&mut self, v: usize, activity: &[f64]) {
+        if let Some(i) = self.positions[v] {
+            self.up(i, activity);
+        }
+    }
+
+    fn up(&mut self, mut i: usize, activity: &[f64]) {
+        let v = self.heap[i];
+
+        while i > 0 {
+            let parent = (i - 1) / 2;
+            if activity[self.heap[parent]] >= activity[v] {
+                break;
+            }
+
+            self.heap[i] = self.heap[parent];
+            self.positions[self.heap[i]] = Some(i);
+            i = parent;
+        }
+
+        self.heap[i] = v;
+        self.positions[v] = Some(i);
+    }
+
+    fn down(&mut self, mut i: usize, activity: &[f64]) {
+        let v = self.heap[i];
+
+        loop {
+            let l = 2 * i + 1;
+            if l >= self.heap.len() {
+                break;
+            }
+
+            let r = l + 1;
+            let child = if r < self.heap.len() && activity[self.heap[r]] > activity[self.heap[l]] {
+                r
+            } else {
+                l
+            };
+
+            if activity[self.heap[child]] <= activity[v] {
+                break;
+            }
+
+            self.heap[i] = self.heap[child];
+            self.positions[self.heap[i]] = Some(i);
+            i = child;
+        }
+
+        self.heap[i] = v;
+        self.positions[v] = Some(i);
+    }
+}
+
+/// Conflict-driven clause learning SAT solver.
+///
+/// Clauses are watched by two literals, so propagation only visits the clauses whose watched
+/// literal became false. Every conflict is analysed up to the first unique implication point, and
+/// the learnt clause backjumps to the second highest decision level involved. Decisions pick the
+/// variable with the highest activity, with the polarity it had when last unassigned, and the
+/// search restarts following the Luby sequence.
+#[derive(Debug)]
+pub struct Solver {
+    clauses: Vec<Vec<Lit>>,
+    /// Clauses watched by each literal, visited when it becomes false.
+    watches: Vec<Vec<usize>>,
+    values: Vec<Option<bool>>,
+    levels: Vec<usize>,
+    reasons: Vec<Option<usize>>,
+    trail: Vec<Lit>,
+    /// Length of the trail at the start of every decision level.
+    limits: Vec<usize>,
+    head: usize,
+    activity: Vec<f64>,
+    increment: f64,
+    heap: Heap,
+    phases: Vec<bool>,
+    seen: Vec<bool>,
+    inconsistent: bool,
+    /// Learnt clauses in the order they were derived, if logging is enabled.
+    proof: Option<Vec<Vec<Lit>>>,
+    /// Assumptions that conflicted in the last unsatisfiable search.
+    failed: Vec<Lit>,
+}
+
+impl Default for Solver {
+    fn default() -> Self {
+        Self {
+            clauses: vec![],
+            watches: vec![],
+            values: vec![],
+            levels: vec![],
+            reasons: vec![],
+            trail: vec![],
+            limits: vec![],
+            head: 0,
+            activity: vec![],
+            // a zero increment would never bump the activities
+            increment: 1.0,
+            heap: Heap::default(),
+            phases: vec![],
+            seen: vec![],
+            inconsistent: false,
+            proof: None,
+            failed: vec![],
+        }
+    }
+}
+
+impl Solver {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load every clause of the CNF.
+    pub fn with_cnf(cnf: &Cnf) -> Self {
+        let mut solver = Self::new();
+
+        solver.reserve(cnf.variables);
+        for clause in &cnf.clauses {
+            solver.add_clause(clause);
+        }
+
+        solver
+    }
+
+    /// Record every learnt clause, so an unsatisfiable result can be checked with
+    /// [`Solver::proof`].
+    pub fn log_proof(&mut self) {
+        self.proof.get_or_insert_with(Vec::new);
+    }
+
+    /// The learnt clauses, in order. Each is implied by unit propagation over the clauses of the
+    /// solver and the previous ones.
+    pub fn proof(&self) -> &[Vec<Lit>] {
+        self.proof.as_deref().unwrap_or_default()
+    }
+
+    /// Ensure the solver has at least `variables` variables.
+    pub fn reserve(&mut self, variables: usize) {
+        while self.values.len() < variables {
+            let v = self.values.len();
+
+            self.values.push(None);
+            self.levels.push(0);
+            self.reasons.push(None);
+            self.activity.push(0.0);
+            self.phases.push(false);
+            self.seen.push(false);
+            self.watches.push(vec![]);
+            self.watches.push(vec![]);
+            self.heap.push(v, &self.activity);
+        }
+    }
+
+    fn value(&self, lit: Lit) -> Option<bool> {
+        self.values[lit.variable()].map(|v| v == lit.is_positive())
+    }
+
+    fn level(&self) -> usize {
+        self.limits.len()
+    }
+
+    /// Add a clause, simplified against the assignments of the root level.
+    ///
+    /// Returns `false` if the clauses became unsatisfiable.
+    pub fn add_clause(&mut self, clause: &[Lit]) -> bool {
+        if self.inconsistent {
+            return false;
+        }
+
+        self.backtrack(0);
+
+        if let Some(max) = clause.iter().map(|l| l.variable()).max() {
+            self.reserve(max + 1);
+        }
+
+        let mut lits = clause.to_vec();
+        lits.sort_unstable();
+        lits.dedup();
+
+        if lits.windows(2).any(|w| w[0] == !w[1])
+            || lits.iter().any(|l| self.value(*l) == Some(true))
+        {
+            return true;
+        }
+
+        lits.retain(|l| self.value(*l).is_none());
+
+        match lits.len() {
+            0 => self.inconsistent = true,
+            1 => {
+                self.assign(lits[0], None);
+                self.inconsistent = self.propagate().is_some();
+            }
+            _ => {
+                self.attach(lits);
+            }
+        }
+
+        !self.inconsistent
+    }
+
+    fn attach(&mut self, clause: Vec<Lit>) -> usize {
+        let id = self.clauses.len();
+
+        self.watches[clause[0].index()].push(id);
+        self.watches[clause[1].index()].push(id);
+        self.clauses.push(clause);
+
+        id
+    }
+
+    fn assign(&mut self, lit: Lit, reason: Option<usize>) {
+        let v = lit.variable();
+
+        self.values[v] = Some(lit.is_positive());
+        self.levels[v] = self.level();
+        self.reasons[v] = reason;
+        self.trail.push(lit);
+    }
+
+    /// Propagate the assignments of the trail, returning the conflicting clause if any.
+    fn propagate(&mut self) -> Option<usize> {
+        while self.head < self.trail.len() {
+            let falsified = !self.trail[self.head];
+            self.head += 1;
+
+            let mut watches = std::mem::take(&mut self.watches[falsified.index()]);
+            let mut conflict = None;
+            let mut kept = 0;
+            let mut i = 0;
+
+            while i < watches.len() {
+                let id = watches[i];
+                i += 1;
+
+                // keep the falsified literal in the second position
+                let clause = &mut self.clauses[id];
+                if clause[0] == falsified {
+                    clause.swap(0, 1);
+                }
+
+                let first = clause[0];
+                if self.values[first.variable()].map(|v| v == first.is_positive()) == Some(true) {
+                    watches[kept] = id;
+                    kept += 1;
+                    continue;
+                }
+
+                let replacement = clause[2..].iter().position(|l| {
+                    self.values[l.variable()].map(|v| v == l.is_positive()) != Some(false)
+                });
+
+                if let Some(k) = replacement {
+                    clause.swap(1, k + 2);
+                    let watch = clause[1];
+                    self.watches[watch.index()].push(id);
+                    continue;
+                }
+
+                watches[kept] = id;
+                kept += 1;
+
+                if self.value(first) == Some(false) {
+                    conflict = Some(id);
+                    while i < watches.len() {
+                        watches[kept] = watches[i];
+                        kept += 1;
+                        i += 1;
+                    }
+                } else {
+                    self.assign(first, Some(id));
+                }
+            }
+
+            watches.truncate(kept);
+            self.watches[falsified.index()] = watches;
+
+            if conflict.is_some() {
+                return conflict;
+            }
+        }
+
+        None
+    }
+
+    /// Derive the first unique implication point clause of the conflict, with the asserting
+    /// literal first and a literal of the backjump level second.
+    fn analyze(&mut self, conflict: usize) -> (Vec<Lit>, usize) {
+        let mut learnt = vec![];
+        let mut pending = 0;
+        let mut index = self.trail.len();
+        let mut implied: Option<Lit> = None;
+        let mut id = conflict;
+
+        loop {
+            let skip = usize::from(implied.is_some());
+            for k in skip..self.clauses[id].len() {
+                let lit = self.clauses[id][k];
+                let v = lit.variable();
+
+                if self.seen[v] || self.levels[v] == 0 {
+                    continue;
+                }
+
+                self.seen[v] = true;
+                self.bump(v);
+
+                if self.levels[v] == self.level() {
+                    pending += 1;
+                } else {
+                    learnt.push(lit);
+                }
+            }
+
+            let lit = loop {
+                index -= 1;
+                if self.seen[self.trail[index].variable()] {
+                    break self.trail[index];
+                }
+            };
+
+            self.seen[lit.variable()] = false;
+            implied = Some(lit);
+            pending -= 1;
+
+            if pending == 0 {
+                break;
+            }
+
+            id = self.reasons[lit.variable()].expect("only decisions have no reason");
+        }
+
+        // drop the literals implied by the other literals of the clause
+        let redundant = learnt
+            .iter()
+            .map(|lit| {
+                self.reasons[lit.variable()].is_some_and(|reason| {
+                    self.clauses[reason][1..]
+                        .iter()
+                        .all(|l| self.seen[l.variable()] || self.levels[l.variable()] == 0)
+                })
+            })
+            .collect::<Vec<_>>();
+
+        for lit in &learnt {
+            self.seen[lit.variable()] = false;
+        }
+
+        let mut learnt = iter::once(!implied.expect("the conflict has a literal"))
+            .chain(
+                learnt
+                    .into_iter()
+                    .zip(redundant)
+                    .filter(|(_, r)| !r)
+                    .map(|(l, _)| l),
+            )
+            .collect::<Vec<_>>();
+
+        let mut level = 0;
+        if learnt.len() > 1 {
+            let (k, max) = learnt
+                .iter()
+                .enumerate()
+                .skip(1)
+                .map(|(k, l)| (k, self.levels[l.variable()]))
+                .max_by_key(|(_, level)| *level)
+                .expect("the clause has a second literal");
+
+            learnt.swap(1, k);
+            level = max;
+        }
+
+        (learnt, level)
+    }
+
+    fn bump(&mut self, v: usize) {
+        self.activity[v] += self.increment;
+
+        if self.activity[v] > 1e100 {
+            for a in &mut self.activity {
+                *a *= 1e-100;
+            }
+            self.increment *= 1e-100;
+        }
+
+        self.heap.increase(v, &self.activity);
+    }
+
+    fn backtrack(&mut self, level: usize) {
+        if self.level() <= level {
+            return;
+        }
+
+        let limit = self.limits[level];
+        for lit in self.trail.drain(limit..) {
+            let v = lit.variable();
+
+            self.values[v] = None;
+            self.reasons[v] = None;
+            self.phases[v] = lit.is_positive();
+            self.heap.push(v, &self.activity);
+        }
+
+        self.limits.truncate(level);
+        self.head = self.trail.len();
+    }
+
+    fn decide(&mut self) -> Option<Lit> {
+        while let Some(v) = self.heap.pop(&self.activity) {
+            if self.values[v].is_none() {
+                return Some(Lit::new(v, self.phases[v]));
+            }
+        }
+
+        None
+    }
+
+    /// Search for an assignment that satisfies every clause.
+    ///
+    /// Returns `true` if one was found, which is then available through [`Solver::model`].
+    pub fn solve(&mut self) -> bool {
+        self.solve_with(&[])
+    }
+
+    /// Search for an assignment that satisfies every clause and the assumed literals.
+    ///
+    /// The assumptions are the first decisions of the search, so the clauses learnt under them
+    /// remain valid without them.
+    pub fn solve_with(&mut self, assumptions: &[Lit]) -> bool {
+        self.failed.clear();
+
+        if self.inconsistent {
+            return false;
+        }
+
+        if let Some(max) = assumptions.iter().map(|l| l.variable()).max() {
+            self.reserve(max + 1);
+        }
+
+        self.backtrack(0);
+
+        let mut conflicts = 0;
+        let mut restarts = 0;
+        let mut limit = RESTART_BASE * luby(restarts);
+
+        loop {
+            if let Some(conflict) = self.propagate() {
+                if self.level() == 0 {
+                    self.inconsistent = true;
+                    return false;
+                }
+
+                let (learnt, level) = self.analyze(conflict);
+                self.backtrack(level);
+
+                if let Some(proof) = &mut self.proof {
+                    proof.push(learnt.clone());
+                }
+
+                if learnt.len() == 1 {
+                    self.assign(learnt[0], None);
+                } else {
+                    let asserting = learnt[0];
+                    let id = self.attach(learnt);
+                    self.assign(asserting, Some(id));
+                }
+
+                self.increment /= ACTIVITY_DECAY;
+                conflicts += 1;
+                continue;
+            }
+
+            if conflicts >= limit {
+                conflicts = 0;
+                restarts += 1;
+                limit = RESTART_BASE * luby(restarts);
+                self.backtrack(0);
+                continue;
+            }
+
+            if let Some(&assumption) = assumptions.get(self.level()) {
+                match self.value(assumption) {
+                    Some(false) => {
+                        self.failed = self.analyze_final(assumption);
+                        return false;
+                    }
+                    Some(true) => self.limits.push(self.trail.len()),
+                    None => {
+                        self.limits.push(self.trail.len());
+                        self.assign(assumption, None);
+                    }
+                }
+                continue;
+            }
+
+            match self.decide() {
+                Some(lit) => {
+                    self.limits.push(self.trail.len());
+                    self.assign(lit, None);
+                }
+                None => return true,
+            }
+        }
+    }
+
+    /// The assumptions that were falsified by the previous ones, including the falsified one.
+    fn analyze_final(&mut self, assumption: Lit) -> Vec<Lit> {
+        let mut core = vec![assumption];
+        let v = assumption.variable();
+
+        if self.levels[v] == 0 {
+            return core;
+        }
+
+        self.seen[v] = true;
+
+        for i in (self.limits[0]..self.trail.len()).rev() {
+            let lit = self.trail[i];
+            let v = lit.variable();
+
+            if !self.seen[v] {
+                continue;
+            }
+
+            self.seen[v] = false;
+
+            match self.reasons[v] {
+                // the decisions of the assumption levels are the assumptions
+                None => core.push(lit),
+                Some(reason) => {
+                    for l in &self.clauses[reason][1..] {
+                        if self.levels[l.variable()] > 0 {
+                            self.seen[l.variable()] = true;
+                        }
+                    }
+                }
+            }
+        }
+
+        core
+    }
+
+    /// The subset of the assumptions of the last unsatisfiable search that suffices for the
+    /// clauses to be unsatisfiable. Empty if they are unsatisfiable without assumptions.
+    pub fn failed(&self) -> &[Lit] {
+        &self.failed
+    }
+
+    /// Value of the variable in the last satisfying assignment.
+    pub fn model(&self, variable: usize) -> Option<bool> {
+        self.values.get(variable).copied().flatten()
+    }
+}
+
+/// The `i`-th element of the Luby sequence `1, 1, 2, 1, 1, 2, 4, 1, ...`.
+fn luby(mut i: u64) -> u64 {
+    let mut size = 1;
+    let mut exponent = 0;
+
+    while size < i + 1 {
+        exponent += 1;
+        size = 2 * size + 1;
+    }
+
+    while size - 1 != i {
+        size = (size - 1) / 2;
+        exponent -= 1;
+        i %= size;
+    }
+
+    1 << exponent
+}
+
+/// Satisfiability checker of instructions, running the [`Solver`] on their Tseitin encoding.
+pub struct Sat;
+
+impl Sat {
+    pub fn satisfy(instruction: &Instruction) -> Option<Model> {
+        let cnf = Cnf::tseitin(instruction);
+        let mut solver = Solver::with_cnf(&cnf);
+
+        solver.solve().then(|| model(&solver, &cnf))
+    }
+
+    /// An assignment of the arguments of both instructions under which they differ.
+    pub fn distinguish(a: &Instruction, b: &Instruction) -> Option<Model> {
+        Self::satisfy(&Instruction::Xor(Box::new(a.clone()), Box::new(b.clone())))
+    }
+}
+
+/// The values of the arguments of the CNF in the last solution of the solver.
+pub fn model(solver: &Solver, cnf: &Cnf) -> Model {
+    let values = cnf
+        .arguments
+        .iter()
+        .enumerate()
+        .map(|(v, name)| (name.clone(), solver.model(v).unwrap_or(false)))
+        .collect();
+
+    Model::new(values)
+}
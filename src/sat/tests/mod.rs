@@ -0,0 +1,262 @@
+use super::*;
+
+/// Deterministic xorshift generator, so the random instances are reproducible.
+struct Random(u64);
+
+impl Random {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn below(&mut self, n: u64) -> u64 {
+        self.next() % n
+    }
+}
+
+fn random_3sat(random: &mut Random, variables: u64, clauses: usize) -> String {
+    (0..clauses)
+        .map(|_| {
+            let clause = (0..3)
+                .map(|_| {
+                    let v = random.below(variables);
+                    let not = if random.below(2) == 0 { "!" } else { "" };
+                    format!("{not}x{v}")
+                })
+                .collect::<Vec<_>>()
+                .join(" v ");
+
+            format!("({clause})")
+        })
+        .collect::<Vec<_>>()
+        .join(" ^ ")
+}
+
+/// Random 3-SAT instance with a planted model, since the first literal of every clause agrees
+/// with a fixed random assignment.
+fn planted_3sat(random: &mut Random, variables: u64, clauses: usize) -> String {
+    let planted = (0..variables)
+        .map(|_| random.below(2) == 1)
+        .collect::<Vec<_>>();
+
+    (0..clauses)
+        .map(|_| {
+            let clause = (0..3)
+                .map(|i| {
+                    let v = random.below(variables);
+                    let positive = match i {
+                        0 => planted[v as usize],
+                        _ => random.below(2) == 1,
+                    };
+                    let not = if positive { "" } else { "!" };
+                    format!("{not}x{v}")
+                })
+                .collect::<Vec<_>>()
+                .join(" v ");
+
+            format!("({clause})")
+        })
+        .collect::<Vec<_>>()
+        .join(" ^ ")
+}
+
+#[test]
+fn luby_sequence() {
+    let sequence = (0..15).map(luby).collect::<Vec<_>>();
+
+    assert_eq!(vec![1, 1, 2, 1, 1, 2, 4, 1, 1, 2, 1, 1, 2, 4, 8], sequence);
+}
+
+#[test]
+fn satisfy_agrees_with_truth_table() {
+    let mut random = Random(0x2545_f491_4f6c_dd1d);
+
+    for _ in 0..200 {
+        let expr = random_3sat(&mut random, 6, 26);
+        let instruction = Instruction::try_from(expr.as_str()).unwrap();
+        let satisfiable = instruction
+            .evaluate()
+            .unwrap()
+            .rows
+            .iter()
+            .any(|r| r[r.len() - 1]);
+
+        match instruction.satisfy() {
+            Some(model) => {
+                assert!(satisfiable, "{expr}");
+                assert_eq!(Ok(true), instruction.evaluate_with(&model), "{expr}");
+            }
+            None => assert!(!satisfiable, "{expr}"),
+        }
+    }
+}
+
+#[test]
+fn satisfy_constants() {
+    let t = Instruction::True.satisfy().unwrap();
+    assert_eq!(0, t.iter().count());
+
+    assert_eq!(None, Instruction::False.satisfy());
+    assert_eq!(None, Instruction::try_from("a ^ !a").unwrap().satisfy());
+}
+
+#[test]
+fn pigeonhole_is_unsatisfiable() {
+    let (pigeons, holes) = (6, 5);
+
+    let placed = (0..pigeons).map(|p| {
+        let clause = (0..holes)
+            .map(|h| format!("p{p}h{h}"))
+            .collect::<Vec<_>>()
+            .join(" v ");
+
+        format!("({clause})")
+    });
+
+    let exclusive = (0..holes).flat_map(|h| {
+        (0..pigeons)
+            .flat_map(move |p| (p + 1..pigeons).map(move |q| format!("!(p{p}h{h} ^ p{q}h{h})")))
+    });
+
+    let expr = placed.chain(exclusive).collect::<Vec<_>>().join(" ^ ");
+    let instruction = Instruction::try_from(expr.as_str()).unwrap();
+
+    assert_eq!(None, instruction.satisfy());
+}
+
+#[test]
+fn satisfy_beyond_truth_tables() {
+    let n = 150;
+    let chain = (0..n - 1)
+        .map(|i| format!("(x{i} -> x{})", i + 1))
+        .collect::<Vec<_>>()
+        .join(" ^ ");
+
+    let sat = Instruction::try_from(format!("x0 ^ {chain}").as_str()).unwrap();
+    let model = sat.satisfy().unwrap();
+    assert_eq!(n, model.iter().count());
+    assert!(model.iter().all(|(_, v)| v));
+
+    let unsat = Instruction::try_from(format!("x0 ^ {chain} ^ !x{}", n - 1).as_str()).unwrap();
+    assert_eq!(None, unsat.satisfy());
+
+    let mut random = Random(0x9e37_79b9_7f4a_7c15);
+    let expr = planted_3sat(&mut random, 120, 400);
+    let instruction = Instruction::try_from(expr.as_str()).unwrap();
+
+    let model = instruction.satisfy().unwrap();
+    assert_eq!(Ok(true), instruction.evaluate_with(&model));
+}
+
+#[test]
+fn model_display() {
+    let model = Model::new(vec![("a".to_string(), true), ("b".to_string(), false)]);
+
+    assert_eq!("a=1, b=0", model.to_string());
+    assert_eq!(Some(false), model.value("b"));
+    assert_eq!(None, model.value("c"));
+}
+
+#[test]
+fn distinguish_formulas() {
+    let a = Instruction::try_from("!(a ^ b) -> c").unwrap();
+    let b = Instruction::try_from("a ^ b v c").unwrap();
+    assert!(a.is_equivalent(&b));
+
+    let c = Instruction::try_from("a ^ c v b").unwrap();
+    let model = a.distinguish(&c).unwrap();
+    assert_ne!(a.evaluate_with(&model), c.evaluate_with(&model));
+
+    let d = Instruction::try_from("a v d").unwrap();
+    let model = Instruction::try_from("a").unwrap().distinguish(&d).unwrap();
+    assert_eq!(Some(false), model.value("a"));
+    assert_eq!(Some(true), model.value("d"));
+}
+
+#[test]
+fn distinguish_beyond_truth_tables() {
+    let n = 120;
+    let names = (0..n).map(|i| format!("x{i}")).collect::<Vec<_>>();
+
+    // the same parity, associated to the left and to the right
+    let left = names.join(" + ");
+    let right = names
+        .iter()
+        .rev()
+        .fold(String::new(), |acc, x| match acc.is_empty() {
+            true => x.clone(),
+            false => format!("({x} + {acc})"),
+        });
+
+    let left = Instruction::try_from(left.as_str()).unwrap();
+    let right = Instruction::try_from(right.as_str()).unwrap();
+    assert!(left.is_equivalent(&right));
+
+    let flipped = Instruction::Not(Box::new(right));
+    let model = left.distinguish(&flipped).unwrap();
+    assert_eq!(n, model.iter().count());
+}
+
+#[test]
+fn solve_under_assumptions() {
+    let instruction = Instruction::try_from("(a -> b) ^ (b -> c) ^ (d v !d)").unwrap();
+    let cnf = Cnf::tseitin(&instruction);
+    let mut solver = Solver::with_cnf(&cnf);
+
+    assert_eq!(vec!["a", "b", "c", "d"], cnf.arguments);
+
+    let (a, c) = (Lit::new(0, true), Lit::new(2, true));
+
+    assert!(solver.solve_with(&[a]));
+    assert_eq!(Some(true), solver.model(2));
+    assert!(!solver.solve_with(&[a, !c]));
+
+    // the assumptions do not persist
+    assert!(solver.solve_with(&[!c]));
+    assert!(solver.solve());
+}
+
+#[test]
+fn failed_assumptions() {
+    let instruction = Instruction::try_from("(a -> b) ^ (b -> c) ^ (d v !d)").unwrap();
+    let cnf = Cnf::tseitin(&instruction);
+    let mut solver = Solver::with_cnf(&cnf);
+
+    assert_eq!(vec!["a", "b", "c", "d"], cnf.arguments);
+
+    let (a, b, c, d) = (
+        Lit::new(0, true),
+        Lit::new(1, true),
+        Lit::new(2, true),
+        Lit::new(3, true),
+    );
+
+    assert!(!solver.solve_with(&[d, a, b, !c]));
+
+    let mut failed = solver.failed().to_vec();
+    failed.sort_unstable();
+    assert!(failed.contains(&!c));
+    assert!(!failed.contains(&d));
+    assert!(!solver.solve_with(&failed));
+
+    assert!(solver.solve_with(&[a]));
+    assert!(solver.failed().is_empty());
+}
+
+#[test]
+fn bump_activities_by_default() {
+    let instruction = Instruction::try_from("(a v b) ^ (a v !b) ^ (!a v b) ^ (!a v !b)").unwrap();
+    let cnf = Cnf::tseitin(&instruction);
+
+    let mut solver = Solver::default();
+    solver.reserve(cnf.variables);
+    for clause in &cnf.clauses {
+        solver.add_clause(clause);
+    }
+
+    assert!(solver.activity.iter().all(|a| *a == 0.0));
+    assert!(!solver.solve());
+    assert!(solver.activity.iter().any(|a| *a > 0.0));
+}
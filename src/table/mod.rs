@@ -0,0 +1,295 @@
+use super::*;
+use bitwise::{Words, LANES, MAX_ARGUMENTS};
+use traverser::Arguments;
+
+#[cfg(test)]
+mod tests;
+
+/// Order of the argument columns of a truth table.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub enum ColumnOrder {
+    /// Sorted by name.
+    #[default]
+    Alphabetical,
+    /// In order of first appearance in the instruction, from left to right.
+    Appearance,
+    /// The listed arguments first, in the given order, followed by the remaining arguments sorted
+    /// by name. Listed names that are not arguments of the instruction are ignored.
+    Explicit(Vec<String>),
+}
+
+impl ColumnOrder {
+    /// Distinct arguments of the instruction, in column order.
+    pub fn arguments<'a>(&self, instruction: &'a Instruction) -> Vec<&'a str> {
+        match self {
+            Self::Alphabetical => Arguments::sorted(instruction),
+            Self::Appearance => Arguments::in_order(instruction),
+            Self::Explicit(names) => {
+                let sorted = Arguments::sorted(instruction);
+                let mut arguments = Vec::with_capacity(sorted.len());
+                for name in names {
+                    match sorted.iter().find(|a| *a == name) {
+                        Some(arg) if !arguments.contains(arg) => arguments.push(*arg),
+                        _ => (),
+                    }
+                }
+
+                for arg in sorted {
+                    if !arguments.contains(&arg) {
+                        arguments.push(arg);
+                    }
+                }
+
+                arguments
+            }
+        }
+    }
+}
+
+/// Order of the rows of a truth table.
+///
+/// The rows are numbered by counting in binary over the argument columns, the first column being
+/// the most significant bit.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum RowOrder {
+    /// From all arguments false to all arguments true.
+    #[default]
+    Ascending,
+    /// From all arguments true to all arguments false.
+    Descending,
+    /// Reflected binary Gray code, starting with all arguments false. Consecutive rows differ in
+    /// exactly one argument.
+    Gray,
+}
+
+impl RowOrder {
+    /// The binary number of the row at the given position of a table with `rows` rows.
+    pub const fn index(&self, position: usize, rows: usize) -> usize {
+        match self {
+            Self::Ascending => position,
+            Self::Descending => rows - 1 - position,
+            Self::Gray => position ^ (position >> 1),
+        }
+    }
+}
+
+/// Selection of the rows of a truth table.
+///
+/// The filter is evaluated alongside the table, so the rows that are filtered out are never
+/// built.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub enum Filter {
+    #[default]
+    All,
+    /// Only the rows that satisfy the instruction.
+    Satisfying,
+    /// Only the rows that falsify the instruction.
+    Falsifying,
+    /// Only the rows where the compared instructions disagree. A single instruction never
+    /// disagrees with itself, so no rows are left.
+    Differing,
+    /// Only the rows that satisfy the side condition. The arguments of the condition that are not
+    /// arguments of the instruction are added as columns.
+    Where(Instruction),
+}
+
+impl Filter {
+    /// The condition that selects the rows of the instruction, if any.
+    pub fn condition(&self, instruction: &Instruction) -> Option<Instruction> {
+        match self {
+            Self::All => None,
+            Self::Satisfying => Some(instruction.clone()),
+            Self::Falsifying => Some(Instruction::Not(Box::new(instruction.clone()))),
+            Self::Differing => Some(Instruction::False),
+            Self::Where(condition) => Some(condition.clone()),
+        }
+    }
+}
+
+/// Layout options of a truth table.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct TableOptions {
+    pub columns: ColumnOrder,
+    pub rows: RowOrder,
+    pub filter: Filter,
+}
+
+/// Truth table generator that honors the [`TableOptions`].
+pub struct TableGenerator;
+
+impl TableGenerator {
+    pub fn run(instruction: &Instruction, options: &TableOptions) -> Result<Table, String> {
+        let condition = options.filter.condition(instruction);
+        let scope = scope(instruction, condition.as_ref());
+        let arguments = options.columns.arguments(&scope);
+
+        Self::generate(
+            arguments,
+            &[("eval".to_string(), instruction)],
+            condition.as_ref(),
+            options,
+        )
+    }
+
+    /// Build a table with one column per distinct subexpression of the instruction, bottom-up.
+    ///
+    /// The argument columns come first, followed by every compound subexpression after its
+    /// operands, the last column being the instruction itself.
+    pub fn steps(instruction: &Instruction, options: &TableOptions) -> Result<Table, String> {
+        let condition = options.filter.condition(instruction);
+        let scope = scope(instruction, condition.as_ref());
+        let arguments = options.columns.arguments(&scope);
+
+        let mut subexpressions = vec![];
+        Self::subexpressions(instruction, &mut subexpressions);
+
+        if subexpressions.is_empty() {
+            subexpressions.push(instruction);
+        }
+
+        let columns = subexpressions
+            .into_iter()
+            .map(|s| (format!("{s:#}"), s))
+            .collect::<Vec<_>>();
+
+        Self::generate(arguments, &columns, condition.as_ref(), options)
+    }
+
+    /// Build a table over the union of the arguments of the instructions, with one result column
+    /// per instruction followed by a `differ` column flagging the rows where they disagree.
+    ///
+    /// [`Filter::Differing`] selects the rows flagged by the `differ` column, while
+    /// [`Filter::Satisfying`] and [`Filter::Falsifying`] select the rows where every instruction
+    /// is true, or every instruction is false.
+    pub fn compare(instructions: &[Instruction], options: &TableOptions) -> Result<Table, String> {
+        let (first, rest) = instructions
+            .split_first()
+            .ok_or("there are no instructions to compare")?;
+
+        let differ = rest
+            .iter()
+            .map(|i| Instruction::Xor(Box::new(first.clone()), Box::new(i.clone())))
+            .reduce(|l, r| Instruction::Or(Box::new(l), Box::new(r)))
+            .unwrap_or(Instruction::False);
+
+        let union = rest.iter().fold(first.clone(), |l, r| {
+            Instruction::And(Box::new(l), Box::new(r.clone()))
+        });
+
+        let condition = match &options.filter {
+            Filter::Differing => Some(differ.clone()),
+            Filter::Falsifying => instructions
+                .iter()
+                .map(|i| Instruction::Not(Box::new(i.clone())))
+                .reduce(|l, r| Instruction::And(Box::new(l), Box::new(r))),
+            filter => filter.condition(&union),
+        };
+        let scope = scope(&union, condition.as_ref());
+        let arguments = options.columns.arguments(&scope);
+
+        let columns = instructions
+            .iter()
+            .map(|i| (format!("{i:#}"), i))
+            .chain(iter::once(("differ".to_string(), &differ)))
+            .collect::<Vec<_>>();
+
+        Self::generate(arguments, &columns, condition.as_ref(), options)
+    }
+
+    fn subexpressions<'a>(instruction: &'a Instruction, acc: &mut Vec<&'a Instruction>) {
+        use Instruction::*;
+        match instruction {
+            True | False | Argument(_) => return,
+            Not(x) => Self::subexpressions(x, acc),
+            And(l, r)
+            | Or(l, r)
+            | Xor(l, r)
+            | Conditional(l, r)
+            | Biconditional(l, r)
+            | Equals(l, r) => {
+                Self::subexpressions(l, acc);
+                Self::subexpressions(r, acc);
+            }
+        }
+
+        if !acc.contains(&instruction) {
+            acc.push(instruction);
+        }
+    }
+
+    /// Build a table with the argument columns followed by one result column per instruction,
+    /// keeping only the rows that satisfy the condition.
+    pub fn generate(
+        arguments: Vec<&str>,
+        columns: &[(String, &Instruction)],
+        condition: Option<&Instruction>,
+        options: &TableOptions,
+    ) -> Result<Table, String> {
+        let n = arguments.len();
+
+        if n > MAX_ARGUMENTS {
+            return Err(format!(
+                "the instruction has {n} arguments; the maximum for a truth table is {MAX_ARGUMENTS}"
+            ));
+        }
+
+        let header = arguments
+            .iter()
+            .map(|a| a.to_string())
+            .chain(columns.iter().map(|(h, _)| h.clone()))
+            .collect();
+
+        let instructions = columns
+            .iter()
+            .map(|(_, i)| *i)
+            .chain(condition)
+            .collect::<Vec<_>>();
+        let mut words = Words::with_instructions(&instructions, arguments)?;
+        let mut current: Option<(usize, Vec<u64>)> = None;
+
+        let len = 1usize << n;
+        let rows = (0..len)
+            .filter_map(|position| {
+                let row = options.rows.index(position, len);
+                let w = row / LANES;
+
+                // every order maps aligned words to aligned words, so the cache is hit for all
+                // the rows of a word
+                let bits = match &current {
+                    Some((c, bits)) if *c == w => bits,
+                    _ => {
+                        let bits = words.words(w).to_vec();
+                        &current.insert((w, bits)).1
+                    }
+                };
+
+                let (bits, mask) = match condition {
+                    Some(_) => bits.split_at(columns.len()),
+                    None => (bits.as_slice(), &[!0][..]),
+                };
+
+                if mask[0] >> (row % LANES) & 1 == 0 {
+                    return None;
+                }
+
+                let row = (0..n)
+                    .map(|j| row >> (n - 1 - j) & 1 == 1)
+                    .chain(bits.iter().map(|b| b >> (row % LANES) & 1 == 1))
+                    .collect();
+
+                Some(row)
+            })
+            .collect();
+
+        Ok(Table { header, rows })
+    }
+}
+
+/// The instruction whose arguments are the columns of the table: the instruction itself,
+/// followed by the condition that filters its rows.
+fn scope(instruction: &Instruction, condition: Option<&Instruction>) -> Instruction {
+    match condition {
+        Some(c) => Instruction::And(Box::new(instruction.clone()), Box::new(c.clone())),
+        None => instruction.clone(),
+    }
+}
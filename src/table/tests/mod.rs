@@ -0,0 +1,253 @@
+use super::*;
+
+fn table(expr: &str, columns: ColumnOrder, rows: RowOrder) -> Table {
+    Instruction::try_from(expr)
+        .unwrap()
+        .table(&TableOptions {
+            columns,
+            rows,
+            ..Default::default()
+        })
+        .unwrap()
+}
+
+#[test]
+fn column_order() {
+    let expr = "c ^ (a v !b)";
+
+    let t = table(expr, ColumnOrder::Alphabetical, RowOrder::Ascending);
+    assert_eq!(vec!["a", "b", "c", "eval"], t.header);
+
+    let t = table(expr, ColumnOrder::Appearance, RowOrder::Ascending);
+    assert_eq!(vec!["c", "a", "b", "eval"], t.header);
+
+    let explicit = ColumnOrder::Explicit(vec!["b".into(), "x".into(), "b".into()]);
+    let t = table(expr, explicit, RowOrder::Ascending);
+    assert_eq!(vec!["b", "a", "c", "eval"], t.header);
+    assert_eq!(vec![false, false, true, true], t.rows[1]);
+}
+
+#[test]
+fn explicit_order_repeated_names() {
+    let explicit = ColumnOrder::Explicit(vec!["a".into(), "b".into(), "a".into()]);
+    let t = table("a ^ b", explicit, RowOrder::Ascending);
+
+    assert_eq!(vec!["a", "b", "eval"], t.header);
+    assert_eq!(4, t.rows.len());
+}
+
+#[test]
+fn row_order() {
+    let expr = "a -> b";
+
+    let t = table(expr, ColumnOrder::Alphabetical, RowOrder::Ascending);
+    assert_eq!(
+        vec![
+            vec![false, false, true],
+            vec![false, true, true],
+            vec![true, false, false],
+            vec![true, true, true],
+        ],
+        t.rows
+    );
+
+    let t = table(expr, ColumnOrder::Alphabetical, RowOrder::Descending);
+    assert_eq!(
+        vec![
+            vec![true, true, true],
+            vec![true, false, false],
+            vec![false, true, true],
+            vec![false, false, true],
+        ],
+        t.rows
+    );
+
+    let t = table(expr, ColumnOrder::Alphabetical, RowOrder::Gray);
+    assert_eq!(
+        vec![
+            vec![false, false, true],
+            vec![false, true, true],
+            vec![true, true, true],
+            vec![true, false, false],
+        ],
+        t.rows
+    );
+}
+
+#[test]
+fn gray_rows_differ_in_one_argument() {
+    let t = table(
+        "(a ^ b ^ c ^ d) v (e + f + g + h)",
+        ColumnOrder::Appearance,
+        RowOrder::Gray,
+    );
+
+    assert_eq!(256, t.rows.len());
+    for pair in t.rows.windows(2) {
+        let changes = pair[0]
+            .iter()
+            .zip(&pair[1])
+            .take(8)
+            .filter(|(a, b)| a != b)
+            .count();
+
+        assert_eq!(1, changes);
+    }
+
+    let instruction = Instruction::try_from("(a ^ b ^ c ^ d) v (e + f + g + h)").unwrap();
+    for row in t.rows {
+        let assignment = t
+            .header
+            .iter()
+            .cloned()
+            .zip(row.iter().copied())
+            .collect::<Vec<_>>();
+
+        assert_eq!(row[8], instruction.evaluate_with(&assignment[..8]).unwrap());
+    }
+}
+
+#[test]
+fn steps() {
+    let instruction = Instruction::try_from("!a v b").unwrap();
+    let t = instruction.steps(&TableOptions::default()).unwrap();
+
+    assert_eq!(vec!["a", "b", "!a", "!a v b"], t.header);
+    assert_eq!(
+        vec![
+            vec![false, false, true, true],
+            vec![false, true, true, true],
+            vec![true, false, false, false],
+            vec![true, true, false, true],
+        ],
+        t.rows
+    );
+}
+
+#[test]
+fn steps_share_subexpressions() {
+    let instruction = Instruction::try_from("(a ^ 'b c') -> !(a ^ 'b c')").unwrap();
+    let t = instruction.steps(&TableOptions::default()).unwrap();
+
+    assert_eq!(
+        vec![
+            "a",
+            "b c",
+            "a ^ \"b c\"",
+            "!(a ^ \"b c\")",
+            "(a ^ \"b c\") -> !(a ^ \"b c\")"
+        ],
+        t.header
+    );
+}
+
+#[test]
+fn steps_of_argument() {
+    let instruction = Instruction::try_from("a").unwrap();
+    let t = instruction.steps(&TableOptions::default()).unwrap();
+
+    assert_eq!(vec!["a", "a"], t.header);
+}
+
+#[test]
+fn filter_rows() {
+    let instruction = Instruction::try_from("a -> b").unwrap();
+    let options = |filter| TableOptions {
+        filter,
+        ..Default::default()
+    };
+
+    let t = instruction.table(&options(Filter::Satisfying)).unwrap();
+    assert_eq!(3, t.rows.len());
+    assert!(t.rows.iter().all(|r| r[2]));
+
+    let t = instruction.table(&options(Filter::Falsifying)).unwrap();
+    assert_eq!(vec![vec![true, false, false]], t.rows);
+
+    let t = instruction.table(&options(Filter::Differing)).unwrap();
+    assert!(t.rows.is_empty());
+
+    let condition = Instruction::try_from("a ^ !c").unwrap();
+    let t = instruction
+        .table(&options(Filter::Where(condition)))
+        .unwrap();
+    assert_eq!(vec!["a", "b", "c", "eval"], t.header);
+    assert_eq!(
+        vec![
+            vec![true, false, false, false],
+            vec![true, true, false, true]
+        ],
+        t.rows
+    );
+}
+
+#[test]
+fn filter_steps() {
+    let instruction = Instruction::try_from("!a v b").unwrap();
+    let t = instruction
+        .steps(&TableOptions {
+            filter: Filter::Falsifying,
+            ..Default::default()
+        })
+        .unwrap();
+
+    assert_eq!(vec![vec![true, false, false, false]], t.rows);
+}
+
+#[test]
+fn compare_formulas() {
+    let instructions = ["a -> b", "!a v c", "!(a ^ !b)"]
+        .into_iter()
+        .map(|i| Instruction::try_from(i).unwrap())
+        .collect::<Vec<_>>();
+
+    let t = Instruction::compare(&instructions, &TableOptions::default()).unwrap();
+    assert_eq!(
+        vec!["a", "b", "c", "a -> b", "!a v c", "!(a ^ !b)", "differ"],
+        t.header
+    );
+    assert_eq!(8, t.rows.len());
+
+    for row in &t.rows {
+        let (a, b, c) = (row[0], row[1], row[2]);
+        assert_eq!(vec![!a || b, !a || c, !a || b], row[3..6]);
+        assert_eq!(row[3] != row[4], row[6]);
+    }
+
+    let t = Instruction::compare(
+        &instructions,
+        &TableOptions {
+            filter: Filter::Differing,
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    assert_eq!(2, t.rows.len());
+    assert!(t.rows.iter().all(|r| r[6]));
+
+    let t = Instruction::compare(
+        &instructions,
+        &TableOptions {
+            filter: Filter::Satisfying,
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    assert_eq!(5, t.rows.len());
+    assert!(t.rows.iter().all(|r| r[3] && r[4] && r[5]));
+
+    let t = Instruction::compare(
+        &instructions,
+        &TableOptions {
+            filter: Filter::Falsifying,
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        vec![vec![true, false, false, false, false, false, false]],
+        t.rows
+    );
+
+    assert!(Instruction::compare(&[], &TableOptions::default()).is_err());
+}
@@ -0,0 +1,370 @@
+use super::*;
+use std::fmt::{self, Write as _};
+use traverser::Arguments;
+
+#[cfg(test)]
+mod tests;
+
+/// Largest number of nodes of a tableau.
+const MAX_NODES: usize = 10_000;
+
+/// End of a fully expanded branch.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Leaf {
+    /// The branch contains a formula and its negation, by number. A constant false formula is its
+    /// own complement.
+    Closed { formula: usize, complement: usize },
+    /// The branch is consistent, and its literals extend to the model, with the arguments that do
+    /// not occur on it set to false.
+    Open(Model),
+}
+
+/// Node of a tableau: the formulas added to the branch by a single rule.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Node {
+    /// The formulas, numbered in the order they were added to the tableau.
+    pub formulas: Vec<(usize, Instruction)>,
+    /// The number of the formula whose rule added the formulas, or `None` at the root.
+    pub source: Option<usize>,
+    /// One child after a rule that extends the branch, and two after a rule that splits it.
+    pub children: Vec<Node>,
+    /// The end of the branch, if the node is a leaf.
+    pub leaf: Option<Leaf>,
+}
+
+impl Node {
+    fn leaves(&self) -> Box<dyn Iterator<Item = &Leaf> + '_> {
+        Box::new(
+            self.leaf
+                .iter()
+                .chain(self.children.iter().flat_map(|c| c.leaves())),
+        )
+    }
+
+    fn lines(&self) -> Vec<String> {
+        let source = self.source.map(|s| format!("  [{s}]")).unwrap_or_default();
+
+        let mut lines = self
+            .formulas
+            .iter()
+            .map(|(n, f)| format!("{n}. {f}{source}"))
+            .collect::<Vec<_>>();
+
+        match &self.leaf {
+            Some(Leaf::Closed {
+                formula,
+                complement,
+            }) if formula == complement => lines.push(format!("x closed by {formula}")),
+            Some(Leaf::Closed {
+                formula,
+                complement,
+            }) => lines.push(format!("x closed by {formula} and {complement}")),
+            Some(Leaf::Open(model)) => lines.push(format!("o open: {model}")),
+            None => (),
+        }
+
+        if let [child] = self.children.as_slice() {
+            lines.extend(child.lines());
+            return lines;
+        }
+
+        let last = self.children.len().saturating_sub(1);
+        for (i, child) in self.children.iter().enumerate() {
+            for (j, line) in child.lines().into_iter().enumerate() {
+                let prefix = match (j, i == last) {
+                    (0, _) => "+- ",
+                    (_, false) => "|  ",
+                    (_, true) => "   ",
+                };
+
+                lines.push(format!("{prefix}{line}"));
+            }
+        }
+
+        lines
+    }
+}
+
+/// Analytic tableau of a set of formulas.
+///
+/// Every branch is expanded until it closes, containing a formula and its negation, or until all
+/// of its formulas are literals. Alpha rules, that extend the branch, are applied before beta
+/// rules, that split it, to keep the tree small. The formulas are satisfiable if and only if a
+/// branch remains open.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Tableau {
+    root: Node,
+}
+
+impl Tableau {
+    /// Expand the tableau of the formulas.
+    ///
+    /// Fails if the tableau has too many nodes to print.
+    pub fn new(formulas: Vec<Instruction>) -> Result<Self, String> {
+        let mut arguments = formulas
+            .iter()
+            .flat_map(Arguments::from)
+            .map(|a| a.to_string())
+            .collect::<Vec<_>>();
+
+        arguments.sort();
+        arguments.dedup();
+
+        let mut builder = Builder {
+            arguments,
+            formulas: 0,
+            nodes: 0,
+        };
+
+        let root = builder.grow(Branch::default(), formulas, None)?;
+
+        Ok(Self { root })
+    }
+
+    pub fn root(&self) -> &Node {
+        &self.root
+    }
+
+    /// Whether every branch is closed, so the formulas are unsatisfiable.
+    pub fn is_closed(&self) -> bool {
+        self.root.leaves().all(|l| matches!(l, Leaf::Closed { .. }))
+    }
+
+    /// The model of the first open branch, if any.
+    pub fn model(&self) -> Option<&Model> {
+        self.root.leaves().find_map(|l| match l {
+            Leaf::Open(model) => Some(model),
+            Leaf::Closed { .. } => None,
+        })
+    }
+
+    /// Graphviz DOT graph of the tableau, with one box per node.
+    pub fn to_dot(&self) -> String {
+        let mut graph = String::new();
+
+        graph.push_str("digraph tableau {\n");
+        graph.push_str("    node [fontname=\"monospace\", shape=box];\n");
+        dot_node(&mut graph, &self.root, &mut 0);
+        graph.push_str("}\n");
+
+        graph
+    }
+}
+
+impl fmt::Display for Tableau {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.root.lines().join("\n"))
+    }
+}
+
+fn dot_node(graph: &mut String, node: &Node, nodes: &mut usize) -> usize {
+    let id = *nodes;
+    *nodes += 1;
+
+    let mut lines = node.lines();
+    let own = node.formulas.len() + usize::from(node.leaf.is_some());
+    lines.truncate(own);
+
+    let label = lines
+        .iter()
+        .map(|l| format!("{}\\l", l.replace('\\', "\\\\").replace('"', "\\\"")))
+        .collect::<String>();
+
+    let style = match &node.leaf {
+        Some(Leaf::Closed { .. }) => ", style=dashed",
+        Some(Leaf::Open(_)) => ", style=bold",
+        None => "",
+    };
+
+    writeln!(graph, "    n{id} [label=\"{label}\"{style}];")
+        .expect("writing to a string is infallible");
+
+    for child in &node.children {
+        let child = dot_node(graph, child, nodes);
+        writeln!(graph, "    n{id} -> n{child};").expect("writing to a string is infallible");
+    }
+
+    id
+}
+
+/// Expansion of a formula.
+enum Rule {
+    /// Every formula is added to the branch.
+    Alpha(Vec<Instruction>),
+    /// The branch splits, with the formulas of either side added to each.
+    Beta(Vec<Instruction>, Vec<Instruction>),
+}
+
+impl Rule {
+    /// The rule of the formula, or `None` for literals and constants.
+    fn of(formula: &Instruction) -> Option<Self> {
+        use Instruction::*;
+
+        let not = |x: &Instruction| Not(Box::new(x.clone()));
+
+        let rule = match formula {
+            True | False | Argument(_) => return None,
+
+            And(l, r) => Self::Alpha(vec![*l.clone(), *r.clone()]),
+            Or(l, r) => Self::Beta(vec![*l.clone()], vec![*r.clone()]),
+            Conditional(l, r) => Self::Beta(vec![not(l)], vec![*r.clone()]),
+            Biconditional(l, r) | Equals(l, r) => {
+                Self::Beta(vec![*l.clone(), *r.clone()], vec![not(l), not(r)])
+            }
+            Xor(l, r) => Self::Beta(vec![*l.clone(), not(r)], vec![not(l), *r.clone()]),
+
+            Not(x) => match &**x {
+                True | False | Argument(_) => return None,
+
+                Not(x) => Self::Alpha(vec![*x.clone()]),
+                And(l, r) => Self::Beta(vec![not(l)], vec![not(r)]),
+                Or(l, r) => Self::Alpha(vec![not(l), not(r)]),
+                Conditional(l, r) => Self::Alpha(vec![*l.clone(), not(r)]),
+                Biconditional(l, r) | Equals(l, r) => {
+                    Self::Beta(vec![*l.clone(), not(r)], vec![not(l), *r.clone()])
+                }
+                Xor(l, r) => Self::Beta(vec![*l.clone(), *r.clone()], vec![not(l), not(r)]),
+            },
+        };
+
+        Some(rule)
+    }
+}
+
+/// The formulas of a branch, with the numbers of the ones that are not expanded yet.
+#[derive(Debug, Clone, Default)]
+struct Branch {
+    formulas: Vec<(usize, Instruction)>,
+    pending: Vec<usize>,
+}
+
+impl Branch {
+    /// Add the formula, returning the number of its complement if it closes the branch.
+    fn add(&mut self, n: usize, formula: Instruction) -> Option<usize> {
+        use Instruction::*;
+
+        if matches!(&formula, False) || matches!(&formula, Not(x) if **x == True) {
+            return Some(n);
+        }
+
+        let complement = self.formulas.iter().find(|(_, g)| {
+            matches!(g, Not(x) if **x == formula) || matches!(&formula, Not(x) if **x == *g)
+        });
+
+        if let Some((m, _)) = complement {
+            return Some(*m);
+        }
+
+        if Rule::of(&formula).is_some() {
+            self.pending.push(n);
+        }
+
+        self.formulas.push((n, formula));
+
+        None
+    }
+
+    /// Remove the next formula to expand, preferring the ones with an alpha rule.
+    fn next(&mut self) -> Option<(usize, Rule)> {
+        let rules = self
+            .pending
+            .iter()
+            .map(|n| {
+                let (_, formula) = self
+                    .formulas
+                    .iter()
+                    .find(|(m, _)| m == n)
+                    .expect("pending formulas are on the branch");
+
+                Rule::of(formula).expect("pending formulas have a rule")
+            })
+            .collect::<Vec<_>>();
+
+        let i = rules
+            .iter()
+            .position(|r| matches!(r, Rule::Alpha(_)))
+            .or_else(|| (!rules.is_empty()).then_some(0))?;
+
+        let n = self.pending.remove(i);
+        let rule = rules.into_iter().nth(i).expect("the position is in bounds");
+
+        Some((n, rule))
+    }
+
+    /// The values of the arguments set by the literals of the branch.
+    fn model(&self, arguments: &[String]) -> Model {
+        let value = |argument: &str| {
+            self.formulas
+                .iter()
+                .any(|(_, f)| matches!(f, Instruction::Argument(a) if a == argument))
+        };
+
+        let values = arguments.iter().map(|a| (a.clone(), value(a))).collect();
+
+        Model::new(values)
+    }
+}
+
+struct Builder {
+    arguments: Vec<String>,
+    formulas: usize,
+    nodes: usize,
+}
+
+impl Builder {
+    fn grow(
+        &mut self,
+        mut branch: Branch,
+        added: Vec<Instruction>,
+        source: Option<usize>,
+    ) -> Result<Node, String> {
+        self.nodes += 1;
+        if self.nodes > MAX_NODES {
+            return Err(format!("the tableau has more than {MAX_NODES} nodes"));
+        }
+
+        let mut formulas = vec![];
+        let mut leaf = None;
+
+        for formula in added {
+            self.formulas += 1;
+            let n = self.formulas;
+
+            if leaf.is_none() {
+                if let Some(complement) = branch.add(n, formula.clone()) {
+                    leaf = Some(Leaf::Closed {
+                        formula: complement,
+                        complement: n,
+                    });
+                }
+            }
+
+            formulas.push((n, formula));
+        }
+
+        let mut node = Node {
+            formulas,
+            source,
+            children: vec![],
+            leaf,
+        };
+
+        if node.leaf.is_some() {
+            return Ok(node);
+        }
+
+        match branch.next() {
+            None => node.leaf = Some(Leaf::Open(branch.model(&self.arguments))),
+            Some((n, Rule::Alpha(added))) => {
+                node.children.push(self.grow(branch, added, Some(n))?);
+            }
+            Some((n, Rule::Beta(left, right))) => {
+                node.children
+                    .push(self.grow(branch.clone(), left, Some(n))?);
+                node.children.push(self.grow(branch, right, Some(n))?);
+            }
+        }
+
+        Ok(node)
+    }
+}
@@ -0,0 +1,109 @@
+use super::*;
+
+fn tableau(formula: &str) -> Tableau {
+    Instruction::try_from(formula).unwrap().tableau().unwrap()
+}
+
+#[test]
+fn tautologies_close() {
+    let tautologies = [
+        "a v !a",
+        "((a -> b) -> a) -> a",
+        "(a <-> b) <-> (!a <-> !b)",
+        "(a + b) <-> !(a = b)",
+        "!(a ^ b) <-> (!a v !b)",
+        "(a -> (b -> c)) -> ((a -> b) -> (a -> c))",
+    ];
+
+    for tautology in tautologies {
+        let tableau = tableau(tautology);
+
+        assert!(tableau.is_closed(), "{tautology}");
+        assert_eq!(None, tableau.model(), "{tautology}");
+    }
+}
+
+#[test]
+fn open_branch_falsifies() {
+    let formulas = ["a -> b", "(a + b) -> (a ^ b)", "(a <-> b) v c", "!(a = a)"];
+
+    for formula in formulas {
+        let instruction = Instruction::try_from(formula).unwrap();
+        let tableau = instruction.tableau().unwrap();
+
+        assert!(!tableau.is_closed(), "{formula}");
+
+        let model = tableau.model().unwrap();
+        assert!(!instruction.evaluate_with(model).unwrap(), "{formula}");
+    }
+}
+
+#[test]
+fn agrees_with_classify() {
+    let formulas = [
+        "(a -> b) ^ (b -> c) -> (a -> c)",
+        "(a v b) ^ (!a v c) -> (b v c)",
+        "(a + b) + (b + a)",
+        "(a ^ b) v (!a ^ !b) v (a + b)",
+        "(a -> b) -> (b -> a)",
+        "a ^ !a",
+    ];
+
+    for formula in formulas {
+        let instruction = Instruction::try_from(formula).unwrap();
+        let tableau = instruction.tableau().unwrap();
+
+        assert_eq!(
+            instruction.classify().is_tautology(),
+            tableau.is_closed(),
+            "{formula}"
+        );
+    }
+}
+
+#[test]
+fn tableau_text() {
+    assert_eq!(
+        r#"1. !((("a" -> "b") -> "a") -> "a")
+2. (("a" -> "b") -> "a")  [1]
+3. !"a"  [1]
++- 4. !("a" -> "b")  [2]
+|  5. "a"  [4]
+|  6. !"b"  [4]
+|  x closed by 3 and 5
++- 7. "a"  [2]
+   x closed by 3 and 7"#,
+        tableau("((a -> b) -> a) -> a").to_string()
+    );
+
+    assert_eq!(
+        r#"1. !("a" -> "b")
+2. "a"  [1]
+3. !"b"  [1]
+o open: a=1, b=0"#,
+        tableau("a -> b").to_string()
+    );
+}
+
+#[test]
+fn tableau_dot() {
+    let dot = tableau("a v !a").to_dot();
+
+    assert!(dot.starts_with("digraph tableau {\n"));
+    assert!(dot.contains(r#"n0 [label="1. !(\"a\" v !\"a\")\l"];"#));
+    assert!(dot.contains("n0 -> n1;"));
+    assert!(dot.contains("x closed by 2 and 3\\l\", style=dashed];"));
+}
+
+#[test]
+fn many_arguments() {
+    // a chain of implications over 200 arguments, far beyond truth tables
+    let chain = (0..200)
+        .map(|i| format!("(x{i} -> x{})", i + 1))
+        .collect::<Vec<_>>()
+        .join(" ^ ");
+    let formula = format!("({chain}) -> (x0 -> x200)");
+
+    let instruction = Instruction::try_from(formula.as_str()).unwrap();
+    assert!(instruction.tableau().unwrap().is_closed());
+}
@@ -0,0 +1,51 @@
+use super::*;
+use Instruction::*;
+
+#[test]
+fn assume_constants() {
+    let instruction = Instruction::try_from("a ^ b").unwrap();
+
+    assert_eq!(Argument("b".into()), instruction.assume(&[("a", true)]));
+
+    let instruction = Instruction::try_from("(a -> b) v c").unwrap();
+
+    assert_eq!(True, instruction.clone().assume(&[("a", false)]));
+    assert_eq!(
+        False,
+        instruction.assume(&[("a", true), ("b", false), ("c", false)])
+    );
+}
+
+#[test]
+fn assume_preserves_semantics() {
+    let instruction = Instruction::try_from("!(a + b) = !c -> (x <-> a)").unwrap();
+    let assumed = [("a", true), ("c", false)];
+    let residual = instruction.clone().assume(&assumed);
+
+    assert!(traverser::Arguments::from(&residual).all(|arg| arg == "b" || arg == "x"));
+
+    for b in [false, true] {
+        for x in [false, true] {
+            let assignment = [("a", true), ("b", b), ("c", false), ("x", x)];
+            let partial = [("b", b), ("x", x)];
+
+            assert_eq!(
+                instruction.evaluate_with(&assignment).unwrap(),
+                residual.evaluate_with(&partial).unwrap()
+            );
+        }
+    }
+}
+
+#[test]
+fn optimize_beyond_truth_tables() {
+    let expr = (0..40)
+        .map(|i| format!("(x{i} v x{i})"))
+        .collect::<Vec<_>>()
+        .join(" ^ ");
+
+    let instruction = Instruction::try_from(expr.as_str()).unwrap();
+    let optimized = instruction.clone().optimize();
+
+    assert!(instruction.is_equivalent(&optimized));
+}
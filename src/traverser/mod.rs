@@ -1,10 +1,8 @@
-use permutator::Combination;
-use std::{
-    collections::{HashMap, HashSet},
-    ops::{Deref, DerefMut},
-};
-
 use super::*;
+use bytecode::Program;
+
+#[cfg(test)]
+mod tests;
 
 const STACK_SIZE: usize = 20;
 
@@ -25,10 +23,7 @@ impl<'a> Iterator for Arguments<'a> {
     type Item = &'a str;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let current = match self.stack.pop() {
-            Some(c) => c,
-            None => return None,
-        };
+        let current = self.stack.pop()?;
 
         use Instruction::*;
         match current {
@@ -53,162 +48,141 @@ impl<'a> Iterator for Arguments<'a> {
 }
 
 impl<'a> Arguments<'a> {
-    /// Returns all possible combinations for the arguments.
-    ///
-    /// # Example
-    ///
-    /// An input `[a, b, c]` will generate the following output:
+    /// Returns the distinct arguments of the instruction, in order of first appearance from left
+    /// to right.
+    pub fn in_order(instruction: &'a Instruction) -> Vec<&'a str> {
+        let mut args = Vec::new();
+        let mut stack = vec![instruction];
+
+        while let Some(current) = stack.pop() {
+            use Instruction::*;
+            match current {
+                Argument(a) => {
+                    if !args.contains(&a.as_str()) {
+                        args.push(a.as_str());
+                    }
+                }
+                True | False => (),
+                Not(x) => stack.push(x),
+                And(l, r)
+                | Or(l, r)
+                | Xor(l, r)
+                | Conditional(l, r)
+                | Biconditional(l, r)
+                | Equals(l, r) => {
+                    stack.push(r);
+                    stack.push(l);
+                }
+            }
+        }
+
+        args
+    }
+
+    /// Returns the distinct arguments of the instruction, sorted alphabetically.
     ///
-    /// []
-    /// [a]
-    /// [a, b]
-    /// [a, c]
-    /// [a, b, c]
-    /// [b]
-    /// [b, c]
-    /// [c]
-    pub fn combinations(instruction: &'a Instruction) -> Vec<Vec<&'a str>> {
-        let args = Self::from(instruction).collect::<HashSet<_>>();
-        let args: Vec<_> = args.into_iter().collect();
-
-        let mut combinations = (1..=args.len())
-            .flat_map(|size| {
-                args.combination(size)
-                    .map(|c| c.iter().map(|v| **v).collect::<Vec<_>>())
-            })
-            .chain(iter::once(vec![]))
-            .collect::<HashSet<_>>()
-            .into_iter()
-            .collect::<Vec<_>>();
-
-        combinations.as_mut_slice().sort();
-        combinations
+    /// This is the same order of the columns of an evaluation table.
+    pub fn sorted(instruction: &'a Instruction) -> Vec<&'a str> {
+        let mut args = Self::from(instruction).collect::<Vec<_>>();
+
+        args.as_mut_slice().sort();
+        args.dedup();
+        args
     }
 }
 
-pub struct Context<'a> {
-    values: HashMap<&'a str, bool>,
+/// Iterator over every assignment of a set of arguments.
+///
+/// The assignments are produced by counting in binary from zero: the row `i` assigns to the
+/// argument `j` the bit `n - 1 - j` of `i`, where `n` is the number of arguments. Hence, the
+/// first argument is the most significant bit and the rows are in ascending binary order, with no
+/// need to sort them.
+///
+/// # Example
+///
+/// An input `[a, b]` will generate the following output:
+///
+/// [(a, 0), (b, 0)]
+/// [(a, 0), (b, 1)]
+/// [(a, 1), (b, 0)]
+/// [(a, 1), (b, 1)]
+pub struct Assignments<'a> {
+    arguments: Vec<&'a str>,
+    row: usize,
+    rows: usize,
 }
 
-impl<'a> Context<'a> {
-    pub fn reset_to_false(&mut self) {
-        self.values.values_mut().for_each(|v| *v = false);
-    }
-}
+impl<'a> Assignments<'a> {
+    pub fn new(arguments: Vec<&'a str>) -> Result<Self, String> {
+        if arguments.len() > bitwise::MAX_ARGUMENTS {
+            return Err(format!(
+                "the instruction has {} arguments; the maximum for a truth table is {}",
+                arguments.len(),
+                bitwise::MAX_ARGUMENTS
+            ));
+        }
 
-impl<'a> Deref for Context<'a> {
-    type Target = HashMap<&'a str, bool>;
+        let rows = 1 << arguments.len();
 
-    fn deref(&self) -> &Self::Target {
-        &self.values
+        Ok(Self {
+            arguments,
+            row: 0,
+            rows,
+        })
     }
-}
 
-impl<'a> DerefMut for Context<'a> {
-    fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.values
-    }
-}
+    pub fn assignment(&self, row: usize) -> impl Iterator<Item = (&'a str, bool)> + '_ {
+        let n = self.arguments.len();
 
-impl<'a> FromIterator<&'a str> for Context<'a> {
-    fn from_iter<T: IntoIterator<Item = &'a str>>(iter: T) -> Self {
-        iter.into_iter().map(|arg| (arg, false)).collect()
+        self.arguments
+            .iter()
+            .enumerate()
+            .map(move |(j, arg)| (*arg, row >> (n - 1 - j) & 1 == 1))
     }
 }
 
-impl<'a> FromIterator<(&'a str, bool)> for Context<'a> {
-    fn from_iter<T: IntoIterator<Item = (&'a str, bool)>>(iter: T) -> Self {
-        Self {
-            values: HashMap::from_iter(iter),
+impl<'a> Iterator for Assignments<'a> {
+    type Item = Vec<(&'a str, bool)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.row >= self.rows {
+            return None;
         }
+
+        let assignment = self.assignment(self.row).collect();
+        self.row += 1;
+
+        Some(assignment)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.rows - self.row;
+        (len, Some(len))
     }
 }
 
+impl<'a> ExactSizeIterator for Assignments<'a> {}
+
 pub struct Evaluator;
 
 impl Evaluator {
     pub fn run(instruction: &Instruction) -> Result<Vec<Evaluation<'_>>, String> {
-        let combinations = Arguments::combinations(instruction);
+        let program = Program::compile(instruction);
+        let assignments = Assignments::new(program.arguments().to_vec())?;
 
-        let args = Arguments::from(instruction);
-        let mut context = Context::from_iter(args);
+        let mut evaluations = Vec::with_capacity(assignments.len());
+        let mut inputs = Vec::with_capacity(program.arguments().len());
+        let mut stack = Vec::new();
 
-        let mut evaluations = Vec::with_capacity(combinations.len());
+        for values in assignments {
+            inputs.clear();
+            inputs.extend(values.iter().map(|(_, v)| *v));
 
-        for case in combinations {
-            context.reset_to_false();
-
-            for value in case {
-                context
-                    .insert(value, true)
-                    .ok_or("the combination provided an invalid argument!")?;
-            }
-
-            let result = Self::run_with_context(instruction, &context)?;
-            let mut values = context.iter().map(|(k, v)| (*k, *v)).collect::<Vec<_>>();
-
-            values.as_mut_slice().sort_by_key(|(k, _)| *k);
+            let result = program.run(&inputs, &mut stack)?;
 
             evaluations.push(Evaluation { values, result });
         }
 
         Ok(evaluations)
     }
-
-    pub fn run_with_context<'a>(
-        instruction: &'a Instruction,
-        context: &'a Context<'a>,
-    ) -> Result<bool, String> {
-        use Instruction::*;
-        match instruction {
-            True => Ok(true),
-            False => Ok(false),
-
-            Argument(arg) => context
-                .get(arg.as_str())
-                .ok_or_else(|| "invalid evaluator context".into())
-                .copied(),
-
-            Not(x) => Ok(!Self::run_with_context(x, context)?),
-
-            And(l, r) => {
-                if !Self::run_with_context(l, context)? {
-                    return Ok(false);
-                }
-                Self::run_with_context(r, context)
-            }
-
-            Or(l, r) => {
-                if Self::run_with_context(l, context)? {
-                    return Ok(true);
-                }
-                Self::run_with_context(r, context)
-            }
-
-            Xor(l, r) => {
-                let l = Self::run_with_context(l, context)?;
-                let r = Self::run_with_context(r, context)?;
-                Ok(l ^ r)
-            }
-
-            Conditional(l, r) => {
-                if !Self::run_with_context(l, context)? {
-                    return Ok(true);
-                }
-                Self::run_with_context(r, context)
-            }
-
-            Biconditional(l, r) => {
-                let l = Self::run_with_context(l, context)?;
-                let r = Self::run_with_context(r, context)?;
-                Ok(l && r || !l && !r)
-            }
-
-            Equals(l, r) => {
-                let l = Self::run_with_context(l, context)?;
-                let r = Self::run_with_context(r, context)?;
-                Ok(l == r)
-            }
-        }
-    }
 }
@@ -0,0 +1,45 @@
+use super::*;
+
+#[test]
+fn assignments_without_arguments() {
+    let assignments = Assignments::new(vec![]).unwrap();
+
+    assert_eq!(1, assignments.len());
+    assert_eq!(
+        vec![Vec::<(&str, bool)>::new()],
+        assignments.collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn assignments_of_one_argument() {
+    let assignments = Assignments::new(vec!["a"]).unwrap();
+
+    assert_eq!(
+        vec![vec![("a", false)], vec![("a", true)]],
+        assignments.collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn assignments_in_ascending_binary_order() {
+    let assignments = Assignments::new(vec!["a", "b", "c"]).unwrap();
+    assert_eq!(8, assignments.len());
+
+    for (i, assignment) in assignments.enumerate() {
+        let expected = vec![("a", i & 4 != 0), ("b", i & 2 != 0), ("c", i & 1 != 0)];
+
+        assert_eq!(expected, assignment);
+    }
+}
+
+#[test]
+fn assignments_of_too_many_arguments() {
+    let names = (0..=bitwise::MAX_ARGUMENTS)
+        .map(|i| format!("x{i}"))
+        .collect::<Vec<_>>();
+
+    let arguments = names.iter().map(|n| n.as_str()).collect::<Vec<_>>();
+    assert!(Assignments::new(arguments[..bitwise::MAX_ARGUMENTS].to_vec()).is_ok());
+    assert!(Assignments::new(arguments).is_err());
+}